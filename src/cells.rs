@@ -13,6 +13,45 @@ use crate::cell_widths::CELL_WIDTHS;
 lazy_static! {
     static ref CACHE: Mutex<LruCache<String, u32>> =
         Mutex::new(LruCache::new(NonZeroUsize::new(4096).unwrap()));
+    static ref GRAPHEME_CACHE: Mutex<LruCache<(WidthPolicy, String), u32>> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(4096).unwrap()));
+}
+
+/// The emoji presentation selector (VS16), which asks for a character to be
+/// shown as an emoji.
+const EMOJI_PRESENTATION_SELECTOR: char = '\u{fe0f}';
+
+/// How to measure a character followed by the emoji presentation selector
+/// (VS16), such as `❤️`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum EmojiPresentation {
+    /// The character takes 2 cells, as an emoji.
+    #[default]
+    Wide,
+    /// The selector is ignored, and the character takes its usual width.
+    Base,
+}
+
+/// How to measure regional indicators, which pair up to make flags such as
+/// `🇬🇧`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum FlagWidth {
+    /// A pair of regional indicators is a single 2 cell flag, and an unpaired
+    /// regional indicator is a 2 cell letter.
+    #[default]
+    Pair,
+    /// Each regional indicator is a 2 cell letter, as drawn by terminals
+    /// without flag support.
+    Letters,
+}
+
+/// How to measure grapheme clusters that terminals disagree on.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct WidthPolicy {
+    /// How to measure characters followed by VS16.
+    pub emoji_presentation: EmojiPresentation,
+    /// How to measure regional indicators.
+    pub flags: FlagWidth,
 }
 
 /// Get number of cells required to display text.
@@ -51,6 +90,116 @@ pub fn cell_len(text: &str) -> u32 {
     total_size
 }
 
+/// Get number of cells required to display text, measuring each extended
+/// grapheme cluster (a character as the user sees it) on its own.
+///
+/// Unlike [`cell_len`], this measures ZWJ sequences, skin tone modifiers, flags
+/// and VS16 sequences as a single emoji.
+///
+/// # Arguments
+///
+/// * `text` - Text to display.
+/// * `policy` - How to measure clusters that terminals disagree on.
+///
+/// # Returns
+///
+/// The number of cells required to display text.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::cells::{cell_len, grapheme_cell_len, WidthPolicy};
+///
+/// let family = "👨\u{200d}👩\u{200d}👧";
+///
+/// assert_eq!(cell_len(family), 6);
+/// assert_eq!(grapheme_cell_len(family, WidthPolicy::default()), 2);
+/// assert_eq!(grapheme_cell_len("🇬🇧 flag", WidthPolicy::default()), 7);
+/// ```
+pub fn grapheme_cell_len(text: &str, policy: WidthPolicy) -> u32 {
+    let mut cache = GRAPHEME_CACHE.lock().expect("Unable to lock LRUCache!");
+    let key = (policy, text.to_owned());
+
+    if let Some(cached_result) = cache.get(&key) {
+        return *cached_result;
+    }
+
+    let graphemes = UnicodeSegmentation::graphemes(text, true);
+    let count = graphemes.clone().count();
+    let total_size = graphemes
+        .map(|grapheme| grapheme_cell_size(grapheme, policy))
+        .sum();
+
+    if count <= 512 {
+        cache.put(key, total_size);
+    }
+
+    total_size
+}
+
+/// Get cell size of an extended grapheme cluster.
+///
+/// A cluster takes the width of its first character that isn't zero width,
+/// except for flags and VS16 sequences, which are measured according to the
+/// policy.
+///
+/// # Arguments
+///
+/// * `grapheme` - A single extended grapheme cluster.
+/// * `policy` - How to measure clusters that terminals disagree on.
+///
+/// # Returns
+///
+/// Number of cells occupied by the cluster.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::cells::{grapheme_cell_size, EmojiPresentation, FlagWidth, WidthPolicy};
+///
+/// let policy = WidthPolicy {
+///     emoji_presentation: EmojiPresentation::Base,
+///     flags: FlagWidth::Letters,
+/// };
+///
+/// assert_eq!(grapheme_cell_size("❤\u{fe0f}", WidthPolicy::default()), 2);
+/// assert_eq!(grapheme_cell_size("❤\u{fe0f}", policy), 1);
+/// assert_eq!(grapheme_cell_size("🇬🇧", policy), 4);
+/// assert_eq!(grapheme_cell_size("🇬", WidthPolicy::default()), 2);
+/// ```
+pub fn grapheme_cell_size(grapheme: &str, policy: WidthPolicy) -> u32 {
+    let regional_indicators = grapheme
+        .chars()
+        .filter(|&c| is_regional_indicator(c))
+        .count();
+    // A cluster holds at most one pair of regional indicators.
+    match (regional_indicators, policy.flags) {
+        (0, _) => {},
+        (1, _) | (_, FlagWidth::Pair) => return 2,
+        (_, FlagWidth::Letters) => return 4,
+    }
+
+    let base = grapheme
+        .chars()
+        .map(get_character_cell_size)
+        .find(|&size| size != 0)
+        .unwrap_or(0);
+
+    if base != 0
+        && policy.emoji_presentation == EmojiPresentation::Wide
+        && grapheme.contains(EMOJI_PRESENTATION_SELECTOR)
+    {
+        base.max(2)
+    } else {
+        base
+    }
+}
+
+/// Check if a character is a regional indicator (`🇦` to `🇿`).
+const fn is_regional_indicator(character: char) -> bool {
+    matches!(character, '\u{1f1e6}'..='\u{1f1ff}')
+}
+
 /// Get cell size of a character.
 ///
 /// # Arguments
@@ -111,6 +260,7 @@ fn get_codepoint_cell_size(codepoint: u32) -> u32 {
 
 /// Set the length of a string to fit within given number of cells.
 #[allow(clippy::missing_panics_doc)]
+#[allow(clippy::double_ended_iterator_last)]
 pub fn set_cell_size(text: String, total: u32) -> String {
     // Regex to match sequence of the most common character ranges.
     if Regex::new(r#"^[\u0020-\u006f\u00a0\u02ff\u0370-\u0482]*$"#)
@@ -211,9 +361,79 @@ mod tests {
     use rstest::rstest;
 
     use super::*;
+    use crate::emoji_codes::EMOJI;
 
     fn test_cell_len_long_string() { assert_eq!(cell_len(&"abc".repeat(200)), 3 * 200) }
 
+    #[rstest]
+    #[case("", 0)]
+    #[case("abc", 3)]
+    #[case("愛", 2)]
+    #[case("e\u{301}", 1)]
+    #[case("\u{fe0f}", 0)]
+    #[case("👨\u{200d}👩\u{200d}👧\u{200d}👦", 2)]
+    #[case("👍🏽", 2)]
+    #[case("🇬🇧", 2)]
+    #[case("🇬", 2)]
+    #[case("🇬🇧🇬", 4)]
+    #[case("❤\u{fe0f}", 2)]
+    #[case("🏳\u{fe0f}\u{200d}🌈", 2)]
+    #[case("a🏴\u{e0067}\u{e0062}\u{e0065}\u{e006e}\u{e0067}\u{e007f}b", 4)]
+    fn test_grapheme_cell_len(#[case] text: &str, #[case] result: u32) {
+        assert_eq!(grapheme_cell_len(text, WidthPolicy::default()), result);
+    }
+
+    #[rstest]
+    #[case(EmojiPresentation::Wide, FlagWidth::Letters, "🇬", 2)]
+    #[case(EmojiPresentation::Wide, FlagWidth::Letters, "🇬🇧🇬", 6)]
+    #[case(EmojiPresentation::Wide, FlagWidth::Letters, "🇬🇧🇬🇧🇬", 10)]
+    #[case(EmojiPresentation::Wide, FlagWidth::Pair, "🇬🇧🇬🇧🇬", 6)]
+    #[case(EmojiPresentation::Base, FlagWidth::Pair, "🇬🇧", 2)]
+    #[case(EmojiPresentation::Base, FlagWidth::Pair, "❤\u{fe0f}", 1)]
+    #[case(EmojiPresentation::Base, FlagWidth::Pair, "🏳\u{fe0f}\u{200d}🌈", 1)]
+    #[case(EmojiPresentation::Base, FlagWidth::Pair, "👍\u{fe0f}", 2)]
+    fn test_grapheme_cell_len_policy(
+        #[case] emoji_presentation: EmojiPresentation,
+        #[case] flags: FlagWidth,
+        #[case] text: &str,
+        #[case] result: u32,
+    ) {
+        let policy = WidthPolicy {
+            emoji_presentation,
+            flags,
+        };
+
+        assert_eq!(grapheme_cell_len(text, policy), result);
+    }
+
+    #[test]
+    fn test_grapheme_cell_len_flags() {
+        let flags: Vec<_> = EMOJI
+            .iter()
+            .filter(|(name, _)| name.starts_with("flag_for_"))
+            .collect();
+        assert!(!flags.is_empty());
+
+        for (name, flag) in flags {
+            assert_eq!(
+                grapheme_cell_len(flag, WidthPolicy::default()),
+                2,
+                "width of {name}"
+            );
+            assert_eq!(
+                grapheme_cell_len(
+                    flag,
+                    WidthPolicy {
+                        flags: FlagWidth::Letters,
+                        ..WidthPolicy::default()
+                    }
+                ),
+                4,
+                "width of {name}"
+            );
+        }
+    }
+
     #[rstest]
     #[case("foo", 0, "")]
     #[case("f", 0, "")]
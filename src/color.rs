@@ -0,0 +1,939 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use lru::LruCache;
+use regex::Regex;
+
+use crate::color_names::ANSI_COLOR_NAMES;
+use crate::color_triplet::{ColorDistance, ColorTriplet};
+use crate::palette::{Palette, ProjectedPalette};
+use crate::palettes::{EIGHT_BIT_PALETTE, STANDARD_PALETTE, WINDOWS_PALETTE};
+use crate::terminal_theme::{TerminalTheme, DEFAULT_TERMINAL_THEME};
+
+lazy_static! {
+    /// Color names mapped to their color numbers.
+    static ref NAME_TO_NUMBER: HashMap<&'static str, u8> = ANSI_COLOR_NAMES.into_iter().collect();
+    /// Color numbers mapped to their canonical names.
+    static ref NUMBER_TO_NAME: HashMap<u8, &'static str> = ANSI_COLOR_NAMES
+        .iter()
+        .rev()
+        .map(|&(name, number)| (number, name))
+        .collect();
+}
+
+/// A truecolor value to downgrade, with the target color system and metric.
+type DowngradeKey = (ColorSystem, ColorDistance, ColorTriplet);
+
+/// Every [`ColorDistance`] metric, to project the palettes for.
+const METRICS: [ColorDistance; 4] = [
+    ColorDistance::Redmean,
+    ColorDistance::Cie76,
+    ColorDistance::Ciede2000,
+    ColorDistance::Oklab,
+];
+
+lazy_static! {
+    /// The 8-bit palette without the 16 standard colors, which depend on the
+    /// terminal's theme.
+    static ref CUBE_PALETTE: Palette<240> = Palette {
+        colors: std::array::from_fn(|index| EIGHT_BIT_PALETTE[index + 16]),
+    };
+    /// The palette of each color system, projected for each metric.
+    static ref PROJECTED_PALETTES: HashMap<(ColorSystem, ColorDistance), ProjectedPalette> =
+        METRICS
+            .into_iter()
+            .flat_map(|metric| {
+                [
+                    (ColorSystem::EightBit, ProjectedPalette::new(&CUBE_PALETTE, metric)),
+                    (ColorSystem::Standard, ProjectedPalette::new(&STANDARD_PALETTE, metric)),
+                    (ColorSystem::Windows, ProjectedPalette::new(&WINDOWS_PALETTE, metric)),
+                ]
+                .map(|(system, palette)| ((system, metric), palette))
+            })
+            .collect();
+    /// Truecolor values already downgraded with an explicit metric, by color
+    /// system and metric.
+    static ref DOWNGRADE_CACHE: Mutex<LruCache<DowngradeKey, u8>> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(4096).unwrap()));
+}
+
+lazy_static! {
+    static ref RE_COLOR: Regex =
+        Regex::new(r#"^#([0-9a-f]{6})$|^color\(([0-9]+)\)$|^rgb\(([\d\s,]+)\)$"#).unwrap();
+}
+
+/// One of the color systems supported by terminals.
+///
+/// A terminal without any color support has no [`ColorSystem`] at all, which
+/// is why it is usually passed around as an `Option<ColorSystem>`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ColorSystem {
+    /// 16 colors.
+    Standard,
+    /// 256 colors.
+    EightBit,
+    /// 16.7 million colors.
+    TrueColor,
+    /// The 16 colors of the legacy Windows console.
+    Windows,
+}
+
+/// The type of color stored in [`Color`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ColorType {
+    /// The terminal's default color.
+    Default,
+    /// One of the 16 standard ANSI colors.
+    Standard,
+    /// One of the 256 colors of the 8-bit palette.
+    EightBit,
+    /// A 24-bit RGB color.
+    TrueColor,
+    /// One of the 16 colors of the legacy Windows console.
+    Windows,
+}
+
+/// The color could not be parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ColorParseError {
+    /// The string isn't a color name, hex code, `color(N)` or `rgb(R,G,B)`.
+    InvalidColor(String),
+    /// The number in `color(N)` is greater than 255.
+    NumberOutOfRange(String),
+    /// `rgb(...)` doesn't have exactly three components.
+    ComponentCount(String),
+    /// A component in `rgb(...)` isn't a number between 0 and 255.
+    ComponentOutOfRange(String),
+}
+
+impl Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidColor(color) => write!(f, "{color:?} is not a valid color"),
+            Self::NumberOutOfRange(color) => {
+                write!(f, "color number must be <= 255 in {color:?}")
+            },
+            Self::ComponentCount(color) => {
+                write!(f, "expected three components in {color:?}")
+            },
+            Self::ComponentOutOfRange(color) => {
+                write!(f, "color components must be <= 255 in {color:?}")
+            },
+        }
+    }
+}
+
+impl Error for ColorParseError {}
+
+/// Terminal color definition.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Color {
+    /// The name of the color (typically the input to [`Color::parse`]).
+    pub name: String,
+    /// The type of the color.
+    pub color_type: ColorType,
+    /// The color number, if a standard or 8-bit color.
+    pub number: Option<u8>,
+    /// A triplet of color components, if an RGB color.
+    pub triplet: Option<ColorTriplet>,
+}
+
+impl Color {
+    /// Instantiate a new [`Color`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the color.
+    /// * `color_type` - The type of the color.
+    /// * `number` - The color number, if a standard or 8-bit color.
+    /// * `triplet` - A triplet of color components, if an RGB color.
+    pub fn new(
+        name: impl Into<String>,
+        color_type: ColorType,
+        number: Option<u8>,
+        triplet: Option<ColorTriplet>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            color_type,
+            number,
+            triplet,
+        }
+    }
+
+    /// Create a [`Color`] number from its 8-bit ANSI number.
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - A number between 0 and 255 inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::{Color, ColorType};
+    ///
+    /// assert_eq!(Color::from_ansi(1).color_type, ColorType::Standard);
+    /// assert_eq!(Color::from_ansi(208).name, "color(208)");
+    /// ```
+    pub fn from_ansi(number: u8) -> Self {
+        Self::new(
+            format!("color({number})"),
+            if number < 16 {
+                ColorType::Standard
+            } else {
+                ColorType::EightBit
+            },
+            Some(number),
+            None,
+        )
+    }
+
+    /// Create a truecolor [`Color`] from a [`ColorTriplet`].
+    ///
+    /// # Arguments
+    ///
+    /// * `triplet` - A color triplet containing red, green and blue components.
+    pub fn from_triplet(triplet: ColorTriplet) -> Self {
+        Self::new(triplet.hex(), ColorType::TrueColor, None, Some(triplet))
+    }
+
+    /// Create a truecolor [`Color`] from its components.
+    ///
+    /// # Arguments
+    ///
+    /// * `red` - Red component.
+    /// * `green` - Green component.
+    /// * `blue` - Blue component.
+    pub fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::from_triplet(ColorTriplet::new(red, green, blue))
+    }
+
+    /// Check if the color is the terminal's default color.
+    pub fn is_default(&self) -> bool { self.color_type == ColorType::Default }
+
+    /// Get the canonical name of a standard or 8-bit color, which
+    /// [`Color::parse`] turns back in to the same color number.
+    ///
+    /// # Returns
+    ///
+    /// The name, or `None` for default and truecolor colors, and the color
+    /// numbers without a name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::Color;
+    ///
+    /// assert_eq!(Color::from_ansi(208).ansi_name(), Some("dark_orange"));
+    /// assert_eq!(Color::parse("gray0").unwrap().ansi_name(), Some("grey0"));
+    /// assert_eq!(Color::parse("#ff8700").unwrap().ansi_name(), None);
+    /// ```
+    pub fn ansi_name(&self) -> Option<&'static str> {
+        match self.color_type {
+            ColorType::Default | ColorType::TrueColor => None,
+            _ => ansi_color_name(self.number?),
+        }
+    }
+
+    /// Get the native [`ColorSystem`] of this color.
+    pub const fn system(&self) -> ColorSystem {
+        match self.color_type {
+            ColorType::Default | ColorType::Standard => ColorSystem::Standard,
+            ColorType::EightBit => ColorSystem::EightBit,
+            ColorType::TrueColor => ColorSystem::TrueColor,
+            ColorType::Windows => ColorSystem::Windows,
+        }
+    }
+
+    /// Get the ANSI escape codes for this color.
+    ///
+    /// # Arguments
+    ///
+    /// * `foreground` - `true` for the foreground color, `false` for the
+    ///   background color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::Color;
+    ///
+    /// assert_eq!(Color::parse("red").unwrap().get_ansi_codes(true), ["31"]);
+    /// assert_eq!(
+    ///     Color::parse("#ff8800").unwrap().get_ansi_codes(false),
+    ///     ["48", "2", "255", "136", "0"]
+    /// );
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_ansi_codes(&self, foreground: bool) -> Vec<String> {
+        match self.color_type {
+            ColorType::Default => vec![if foreground { "39" } else { "49" }.into()],
+            ColorType::Standard | ColorType::Windows => {
+                let number = self.number.unwrap();
+                let (base, bright_base) = if foreground { (30, 82) } else { (40, 92) };
+
+                vec![if number < 8 {
+                    base + number
+                } else {
+                    bright_base + number
+                }
+                .to_string()]
+            },
+            ColorType::EightBit => vec![
+                if foreground { "38" } else { "48" }.into(),
+                "5".into(),
+                self.number.unwrap().to_string(),
+            ],
+            ColorType::TrueColor => {
+                let triplet = self.triplet.unwrap();
+
+                vec![
+                    if foreground { "38" } else { "48" }.into(),
+                    "2".into(),
+                    triplet.red.to_string(),
+                    triplet.green.to_string(),
+                    triplet.blue.to_string(),
+                ]
+            },
+        }
+    }
+
+    /// Downgrade a color system to a system with fewer colors.
+    ///
+    /// Truecolor values are mapped to the closest color in the 256 color cube
+    /// (or grayscale ramp) for [`ColorSystem::EightBit`], and to the closest
+    /// entry of the standard or Windows palette for [`ColorSystem::Standard`]
+    /// and [`ColorSystem::Windows`]. Colors that already fit in `system` are
+    /// returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `system` - The color system to downgrade to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::{Color, ColorSystem, ColorType};
+    ///
+    /// let color = Color::parse("#ff0000").unwrap();
+    ///
+    /// assert_eq!(
+    ///     color.downgrade(ColorSystem::EightBit),
+    ///     Color::new("#ff0000", ColorType::EightBit, Some(196), None)
+    /// );
+    /// assert_eq!(
+    ///     color.downgrade(ColorSystem::Standard),
+    ///     Color::new("#ff0000", ColorType::Standard, Some(1), None)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn downgrade(&self, system: ColorSystem) -> Self { self.downgrade_by(system, None) }
+
+    /// Downgrade a color system to a system with fewer colors, picking the
+    /// palette entry that is closest according to `metric`.
+    ///
+    /// Unlike [`Color::downgrade`], truecolor values are matched against every
+    /// color of the 256 color cube and grayscale ramp for
+    /// [`ColorSystem::EightBit`]. The palettes are projected for each metric
+    /// once and results are cached, so downgrading many
+    /// pixels of an image or a gradient is cheap.
+    ///
+    /// # Arguments
+    ///
+    /// * `system` - The color system to downgrade to.
+    /// * `metric` - How to measure the distance between colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::{Color, ColorSystem, ColorType};
+    /// use wealthy::color_triplet::ColorDistance;
+    ///
+    /// let color = Color::parse("#000040").unwrap();
+    ///
+    /// assert_eq!(
+    ///     color.downgrade_with(ColorSystem::Standard, ColorDistance::Redmean),
+    ///     Color::new("#000040", ColorType::Standard, Some(0), None)
+    /// );
+    /// assert_eq!(
+    ///     color.downgrade_with(ColorSystem::Standard, ColorDistance::Ciede2000),
+    ///     Color::new("#000040", ColorType::Standard, Some(4), None)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn downgrade_with(&self, system: ColorSystem, metric: ColorDistance) -> Self {
+        self.downgrade_by(system, Some(metric))
+    }
+
+    /// Downgrade a color, with the HSL based 8-bit conversion from Rich and the
+    /// redmean metric if `metric` is `None`.
+    #[allow(clippy::missing_panics_doc)]
+    fn downgrade_by(&self, system: ColorSystem, metric: Option<ColorDistance>) -> Self {
+        if self.color_type == ColorType::Default || self.system() == system {
+            return self.clone();
+        }
+
+        // The triplet is always present for truecolor, and the number for all the
+        // other non-default types.
+        let triplet = match self.triplet {
+            Some(triplet) if self.system() == ColorSystem::TrueColor => triplet,
+            _ => EIGHT_BIT_PALETTE[usize::from(self.number.unwrap())],
+        };
+
+        match system {
+            ColorSystem::EightBit if self.system() == ColorSystem::TrueColor => {
+                let number = closest_number(system, metric, triplet);
+
+                Self::new(self.name.clone(), ColorType::EightBit, Some(number), None)
+            },
+            ColorSystem::Standard => {
+                let number = closest_number(system, metric, triplet);
+
+                Self::new(self.name.clone(), ColorType::Standard, Some(number), None)
+            },
+            ColorSystem::Windows => {
+                if self.system() == ColorSystem::Standard {
+                    return Self::new(self.name.clone(), ColorType::Windows, self.number, None);
+                }
+                let number = closest_number(system, metric, triplet);
+
+                Self::new(self.name.clone(), ColorType::Windows, Some(number), None)
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// Get an equivalent truecolor triplet, resolving the standard colors and
+    /// the default color through a terminal theme.
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - The theme to resolve colors with, or `None` for the default
+    ///   theme.
+    /// * `foreground` - `true` to resolve the default color as a foreground
+    ///   color, `false` as a background color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::Color;
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// assert_eq!(
+    ///     Color::parse("red").unwrap().get_truecolor(None, true),
+    ///     ColorTriplet::new(128, 0, 0)
+    /// );
+    /// assert_eq!(
+    ///     Color::parse("default").unwrap().get_truecolor(None, false),
+    ///     ColorTriplet::new(255, 255, 255)
+    /// );
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_truecolor(&self, theme: Option<&TerminalTheme>, foreground: bool) -> ColorTriplet {
+        let theme = theme.unwrap_or(&DEFAULT_TERMINAL_THEME);
+
+        match self.color_type {
+            ColorType::Default if foreground => theme.foreground_color,
+            ColorType::Default => theme.background_color,
+            ColorType::Standard => theme.ansi_colors[usize::from(self.number.unwrap())],
+            ColorType::EightBit => match self.number.unwrap() {
+                number @ 0..=15 => theme.ansi_colors[usize::from(number)],
+                number => EIGHT_BIT_PALETTE[usize::from(number)],
+            },
+            ColorType::TrueColor => self.triplet.unwrap(),
+            ColorType::Windows => WINDOWS_PALETTE[usize::from(self.number.unwrap())],
+        }
+    }
+
+    /// Parse a color definition.
+    ///
+    /// Accepts `default`, a standard color name such as `red` or
+    /// `bright_magenta`, a CSS style hex code (`#ff8800`), `rgb(R,G,B)` and
+    /// `color(N)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color definition.
+    ///
+    /// # Errors
+    ///
+    /// [`ColorParseError`] if the definition isn't a valid color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::{Color, ColorType};
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// assert_eq!(Color::parse("red").unwrap().number, Some(1));
+    /// assert_eq!(
+    ///     Color::parse("#ff8800").unwrap().triplet,
+    ///     Some(ColorTriplet::new(255, 136, 0))
+    /// );
+    /// assert_eq!(
+    ///     Color::parse("color(208)").unwrap().color_type,
+    ///     ColorType::EightBit
+    /// );
+    /// assert!(Color::parse("rgb(256,0,0)").is_err());
+    /// ```
+    pub fn parse(color: &str) -> Result<Self, ColorParseError> {
+        let original = color;
+        let color = color.trim().to_lowercase();
+
+        if color == "default" {
+            return Ok(Self::new(color, ColorType::Default, None, None));
+        }
+
+        if let Some(&number) = NAME_TO_NUMBER.get(color.as_str()) {
+            let color_type = if number < 16 {
+                ColorType::Standard
+            } else {
+                ColorType::EightBit
+            };
+
+            return Ok(Self::new(color, color_type, Some(number), None));
+        }
+
+        let captures = RE_COLOR
+            .captures(&color)
+            .ok_or_else(|| ColorParseError::InvalidColor(original.into()))?;
+
+        if let Some(color_24) = captures.get(1) {
+            let triplet = parse_rgb_hex(color_24.as_str());
+
+            return Ok(Self::new(color, ColorType::TrueColor, None, Some(triplet)));
+        }
+
+        if let Some(color_8) = captures.get(2) {
+            let number: u8 = color_8
+                .as_str()
+                .parse()
+                .map_err(|_| ColorParseError::NumberOutOfRange(original.into()))?;
+            let color_type = if number < 16 {
+                ColorType::Standard
+            } else {
+                ColorType::EightBit
+            };
+
+            return Ok(Self::new(color, color_type, Some(number), None));
+        }
+
+        let components: Vec<&str> = captures[3].split(',').collect();
+        if components.len() != 3 {
+            return Err(ColorParseError::ComponentCount(original.into()));
+        }
+
+        let components = components
+            .into_iter()
+            .map(|component| component.trim().parse::<u8>())
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| ColorParseError::ComponentOutOfRange(original.into()))?;
+
+        Ok(Self::new(
+            color,
+            ColorType::TrueColor,
+            None,
+            Some(ColorTriplet::new(
+                components[0],
+                components[1],
+                components[2],
+            )),
+        ))
+    }
+}
+
+impl Default for Color {
+    /// Get a [`Color`] instance representing the terminal's default color.
+    fn default() -> Self { Self::new("default", ColorType::Default, None, None) }
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(color: &str) -> Result<Self, Self::Err> { Self::parse(color) }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.name) }
+}
+
+/// Get the color number of a named color, such as `red` or `steel_blue1`.
+///
+/// # Arguments
+///
+/// * `name` - Name of the color, in any case.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::color::ansi_color_number;
+///
+/// assert_eq!(ansi_color_number("Steel_Blue1"), Some(81));
+/// assert_eq!(ansi_color_number("rebecca_purple"), None);
+/// ```
+pub fn ansi_color_number(name: &str) -> Option<u8> {
+    NAME_TO_NUMBER
+        .get(name.trim().to_lowercase().as_str())
+        .copied()
+}
+
+/// Get the canonical name of a color number.
+///
+/// # Arguments
+///
+/// * `number` - The color number.
+///
+/// # Returns
+///
+/// The name, or `None` if the color number has no name.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::color::ansi_color_name;
+///
+/// assert_eq!(ansi_color_name(1), Some("red"));
+/// assert_eq!(ansi_color_name(166), Some("dark_orange3"));
+/// assert_eq!(ansi_color_name(130), None);
+/// ```
+pub fn ansi_color_name(number: u8) -> Option<&'static str> { NUMBER_TO_NAME.get(&number).copied() }
+
+/// Get all the color names along with their color numbers.
+pub fn ansi_color_names() -> &'static [(&'static str, u8)] { &ANSI_COLOR_NAMES }
+
+/// Get the number of the closest color in the 256 color cube or grayscale
+/// ramp.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn eight_bit_number(triplet: &ColorTriplet) -> u8 {
+    let (red, green, blue) = triplet.normalized();
+    let (max, min) = (red.max(green).max(blue), red.min(green).min(blue));
+    let lightness = (max + min) / 2.0;
+    let saturation = if (max - min).abs() < f32::EPSILON {
+        0.0
+    } else if lightness <= 0.5 {
+        (max - min) / (max + min)
+    } else {
+        (max - min) / (2.0 - max - min)
+    };
+
+    if saturation < 0.15 {
+        let gray = (lightness * 25.0).round_ties_even() as u8;
+
+        return match gray {
+            0 => 16,
+            25 => 231,
+            _ => 231 + gray,
+        };
+    }
+
+    let six = |component: u8| {
+        let component = f32::from(component);
+        let six = if component < 95.0 {
+            component / 95.0
+        } else {
+            1.0 + (component - 95.0) / 40.0
+        };
+
+        six.round_ties_even() as u8
+    };
+
+    16 + 36 * six(triplet.red) + 6 * six(triplet.green) + six(triplet.blue)
+}
+
+/// Get the number of the closest color to a triplet in a color system.
+///
+/// The default `metric` is cheap enough to compute directly, so only the
+/// results of an explicit metric are remembered.
+#[allow(clippy::cast_possible_truncation)]
+fn closest_number(system: ColorSystem, metric: Option<ColorDistance>, triplet: ColorTriplet) -> u8 {
+    let Some(metric) = metric else {
+        return match system {
+            ColorSystem::EightBit => eight_bit_number(&triplet),
+            ColorSystem::Windows => WINDOWS_PALETTE
+                .closest((triplet.red, triplet.green, triplet.blue))
+                .unwrap_or_default() as u8,
+            _ => STANDARD_PALETTE
+                .closest((triplet.red, triplet.green, triplet.blue))
+                .unwrap_or_default() as u8,
+        };
+    };
+    let system = match system {
+        ColorSystem::EightBit | ColorSystem::Windows => system,
+        _ => ColorSystem::Standard,
+    };
+    let key = (system, metric, triplet);
+
+    if let Some(number) = DOWNGRADE_CACHE
+        .lock()
+        .expect("Unable to lock LRUCache!")
+        .get(&key)
+    {
+        return *number;
+    }
+
+    let number = PROJECTED_PALETTES[&(system, metric)]
+        .closest(&triplet)
+        .unwrap_or_default() as u8;
+    let number = if system == ColorSystem::EightBit {
+        16 + number
+    } else {
+        number
+    };
+    DOWNGRADE_CACHE
+        .lock()
+        .expect("Unable to lock LRUCache!")
+        .put(key, number);
+
+    number
+}
+
+/// Parse six hex characters in to a [`ColorTriplet`].
+fn parse_rgb_hex(hex_color: &str) -> ColorTriplet {
+    let component = |index: usize| u8::from_str_radix(&hex_color[index..index + 2], 16).unwrap();
+
+    ColorTriplet::new(component(0), component(2), component(4))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("default", Color::new("default", ColorType::Default, None, None))]
+    #[case("red", Color::new("red", ColorType::Standard, Some(1), None))]
+    #[case(
+        "bright_red",
+        Color::new("bright_red", ColorType::Standard, Some(9), None)
+    )]
+    #[case(
+        "  Bright_Magenta ",
+        Color::new("bright_magenta", ColorType::Standard, Some(13), None)
+    )]
+    #[case("color(1)", Color::new("color(1)", ColorType::Standard, Some(1), None))]
+    #[case(
+        "color(17)",
+        Color::new("color(17)", ColorType::EightBit, Some(17), None)
+    )]
+    #[case(
+        "#ff0000",
+        Color::new(
+            "#ff0000",
+            ColorType::TrueColor,
+            None,
+            Some(ColorTriplet::new(255, 0, 0))
+        )
+    )]
+    #[case(
+        "rgb(1,2,3)",
+        Color::new(
+            "rgb(1,2,3)",
+            ColorType::TrueColor,
+            None,
+            Some(ColorTriplet::new(1, 2, 3))
+        )
+    )]
+    #[case(
+        "rgb(10, 20, 30)",
+        Color::new(
+            "rgb(10, 20, 30)",
+            ColorType::TrueColor,
+            None,
+            Some(ColorTriplet::new(10, 20, 30))
+        )
+    )]
+    fn test_parse_success(#[case] color: &str, #[case] result: Color) {
+        assert_eq!(Color::parse(color).unwrap(), result);
+    }
+
+    #[rstest]
+    #[case("256", ColorParseError::InvalidColor("256".into()))]
+    #[case("color(256)", ColorParseError::NumberOutOfRange("color(256)".into()))]
+    #[case("color(1000)", ColorParseError::NumberOutOfRange("color(1000)".into()))]
+    #[case("color(99999999999999999999)", ColorParseError::NumberOutOfRange("color(99999999999999999999)".into()))]
+    #[case("#fffffg", ColorParseError::InvalidColor("#fffffg".into()))]
+    #[case("#ff", ColorParseError::InvalidColor("#ff".into()))]
+    #[case("rgb(1,2)", ColorParseError::ComponentCount("rgb(1,2)".into()))]
+    #[case("rgb(1,2,3,4)", ColorParseError::ComponentCount("rgb(1,2,3,4)".into()))]
+    #[case("rgb(999,0,0)", ColorParseError::ComponentOutOfRange("rgb(999,0,0)".into()))]
+    #[case("rgb(1,,3)", ColorParseError::ComponentOutOfRange("rgb(1,,3)".into()))]
+    #[case("nosuchcolor", ColorParseError::InvalidColor("nosuchcolor".into()))]
+    fn test_parse_error(#[case] color: &str, #[case] error: ColorParseError) {
+        assert_eq!(Color::parse(color), Err(error));
+    }
+
+    #[test]
+    fn test_from_triplet() {
+        assert_eq!(
+            Color::from_triplet(ColorTriplet::new(16, 32, 64)),
+            Color::new(
+                "#102040",
+                ColorType::TrueColor,
+                None,
+                Some(ColorTriplet::new(16, 32, 64))
+            )
+        );
+    }
+
+    #[test]
+    fn test_from_ansi() {
+        assert_eq!(
+            Color::from_ansi(1),
+            Color::new("color(1)", ColorType::Standard, Some(1), None)
+        );
+        assert_eq!(
+            Color::from_ansi(208),
+            Color::new("color(208)", ColorType::EightBit, Some(208), None)
+        );
+    }
+
+    #[rstest]
+    #[case("#000000", ColorSystem::EightBit, ColorType::EightBit, 16)]
+    #[case("#ffffff", ColorSystem::EightBit, ColorType::EightBit, 231)]
+    #[case("#404142", ColorSystem::EightBit, ColorType::EightBit, 237)]
+    #[case("#ff0000", ColorSystem::EightBit, ColorType::EightBit, 196)]
+    #[case("#ff0000", ColorSystem::Standard, ColorType::Standard, 1)]
+    #[case("color(9)", ColorSystem::Standard, ColorType::Standard, 9)]
+    #[case("color(9)", ColorSystem::EightBit, ColorType::Standard, 9)]
+    #[case("#00ff00", ColorSystem::Standard, ColorType::Standard, 2)]
+    #[case("color(20)", ColorSystem::Standard, ColorType::Standard, 4)]
+    #[case("color(20)", ColorSystem::Windows, ColorType::Windows, 4)]
+    #[case("color(20)", ColorSystem::TrueColor, ColorType::EightBit, 20)]
+    #[case("color(9)", ColorSystem::Windows, ColorType::Windows, 9)]
+    #[case("#ff0000", ColorSystem::Windows, ColorType::Windows, 1)]
+    fn test_downgrade(
+        #[case] color: &str,
+        #[case] system: ColorSystem,
+        #[case] color_type: ColorType,
+        #[case] number: u8,
+    ) {
+        assert_eq!(
+            Color::parse(color).unwrap().downgrade(system),
+            Color::new(color, color_type, Some(number), None)
+        );
+    }
+
+    #[rstest]
+    #[case("#ff0000", ColorSystem::EightBit, ColorDistance::Redmean, 196)]
+    #[case("#ff0000", ColorSystem::EightBit, ColorDistance::Ciede2000, 196)]
+    #[case("#000000", ColorSystem::EightBit, ColorDistance::Oklab, 16)]
+    #[case("#808080", ColorSystem::EightBit, ColorDistance::Cie76, 244)]
+    #[case("#000040", ColorSystem::Standard, ColorDistance::Redmean, 0)]
+    #[case("#000040", ColorSystem::Standard, ColorDistance::Oklab, 4)]
+    #[case("#000040", ColorSystem::Windows, ColorDistance::Ciede2000, 0)]
+    #[case("color(20)", ColorSystem::Standard, ColorDistance::Oklab, 4)]
+    fn test_downgrade_with(
+        #[case] color: &str,
+        #[case] system: ColorSystem,
+        #[case] metric: ColorDistance,
+        #[case] number: u8,
+    ) {
+        let color = Color::parse(color).unwrap();
+        let downgraded = color.downgrade_with(system, metric);
+
+        assert_eq!(downgraded.number, Some(number));
+        // The second call is answered from the cache.
+        assert_eq!(color.downgrade_with(system, metric), downgraded);
+    }
+
+    #[rstest]
+    #[case("default", true, &["39"])]
+    #[case("default", false, &["49"])]
+    #[case("red", true, &["31"])]
+    #[case("red", false, &["41"])]
+    #[case("color(9)", true, &["91"])]
+    #[case("color(9)", false, &["101"])]
+    #[case("color(100)", true, &["38", "5", "100"])]
+    #[case("color(100)", false, &["48", "5", "100"])]
+    #[case("#1a2b3c", true, &["38", "2", "26", "43", "60"])]
+    #[case("#1a2b3c", false, &["48", "2", "26", "43", "60"])]
+    fn test_get_ansi_codes(#[case] color: &str, #[case] foreground: bool, #[case] codes: &[&str]) {
+        assert_eq!(
+            Color::parse(color).unwrap().get_ansi_codes(foreground),
+            codes
+        );
+    }
+
+    #[test]
+    fn test_downgrade_default() {
+        assert_eq!(
+            Color::default().downgrade(ColorSystem::Windows),
+            Color::default()
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        assert!(Color::default().is_default());
+        assert_eq!("default".parse::<Color>().unwrap(), Color::default());
+    }
+
+    #[rstest]
+    #[case("dark_orange3", ColorType::EightBit, 166)]
+    #[case("gray100", ColorType::EightBit, 231)]
+    #[case("bright_white", ColorType::Standard, 15)]
+    #[case(" Light_Slate_Gray ", ColorType::EightBit, 103)]
+    fn test_parse_name(#[case] color: &str, #[case] color_type: ColorType, #[case] number: u8) {
+        let color = Color::parse(color).unwrap();
+
+        assert_eq!(color.color_type, color_type);
+        assert_eq!(color.number, Some(number));
+    }
+
+    #[test]
+    fn test_ansi_color_names() {
+        assert_eq!(ansi_color_names().len(), 235);
+
+        for &(name, number) in ansi_color_names() {
+            assert_eq!(ansi_color_number(name), Some(number), "{name}");
+
+            let canonical = ansi_color_name(number).unwrap();
+            assert_eq!(ansi_color_number(canonical), Some(number), "{name}");
+        }
+    }
+
+    #[rstest]
+    #[case(16, Some("grey0"))]
+    #[case(103, Some("light_slate_grey"))]
+    #[case(81, Some("steel_blue1"))]
+    #[case(22, Some("dark_green"))]
+    #[case(255, Some("grey93"))]
+    #[case(130, None)]
+    fn test_ansi_color_name(#[case] number: u8, #[case] name: Option<&str>) {
+        assert_eq!(ansi_color_name(number), name);
+    }
+
+    #[test]
+    fn test_ansi_name() {
+        for number in 0..=255 {
+            let color = Color::from_ansi(number);
+
+            if let Some(name) = color.ansi_name() {
+                assert_eq!(Color::parse(name).unwrap().number, Some(number));
+            }
+        }
+        assert_eq!(
+            Color::parse("color(9)")
+                .unwrap()
+                .downgrade(ColorSystem::Windows)
+                .ansi_name(),
+            Some("bright_red")
+        );
+        assert_eq!(Color::default().ansi_name(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Color::parse("#FF0000").unwrap().to_string(), "#ff0000");
+        assert_eq!(
+            ColorParseError::InvalidColor("foo".into()).to_string(),
+            "\"foo\" is not a valid color"
+        );
+    }
+}
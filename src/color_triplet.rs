@@ -1,14 +1,70 @@
-#![allow(dead_code)]
+/// The minimum contrast ratio between text and its background for WCAG level
+/// AA, for text under 18 points (or 14 points when bold).
+pub const WCAG_AA_CONTRAST_RATIO: f64 = 4.5;
+
+/// A way to measure how different two colors look.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ColorDistance {
+    /// A weighted RGB distance that accounts for the mean red level. Cheap, and
+    /// the metric used by Rich.
+    #[default]
+    Redmean,
+    /// Euclidean distance in the CIE L\*a\*b\* color space (ΔE 1976).
+    Cie76,
+    /// The CIEDE2000 color difference (ΔE 2000). The most accurate, and the
+    /// most expensive to compute.
+    Ciede2000,
+    /// Euclidean distance in the OKLab color space.
+    Oklab,
+}
+
+impl ColorDistance {
+    /// Get the coordinates of a color in the space this metric works in.
+    pub(crate) fn project(self, color: &ColorTriplet) -> (f64, f64, f64) {
+        match self {
+            Self::Redmean => (
+                f64::from(color.red),
+                f64::from(color.green),
+                f64::from(color.blue),
+            ),
+            Self::Cie76 | Self::Ciede2000 => color.to_lab(),
+            Self::Oklab => color.to_oklab(),
+        }
+    }
+
+    /// Get the distance between two colors previously passed through
+    /// [`ColorDistance::project`].
+    pub(crate) fn between(self, color1: (f64, f64, f64), color2: (f64, f64, f64)) -> f64 {
+        match self {
+            Self::Redmean => {
+                let red_mean = ((color1.0 + color2.0) / 2.0).floor();
+                let red = color1.0 - color2.0;
+                let green = color1.1 - color2.1;
+                let blue = color1.2 - color2.2;
+
+                (((512.0 + red_mean) * red * red / 256.0).floor()
+                    + 4.0 * green * green
+                    + ((767.0 - red_mean) * blue * blue / 256.0).floor())
+                .sqrt()
+            },
+            Self::Cie76 | Self::Oklab => ((color1.0 - color2.0).powi(2)
+                + (color1.1 - color2.1).powi(2)
+                + (color1.2 - color2.2).powi(2))
+            .sqrt(),
+            Self::Ciede2000 => ciede2000(color1, color2),
+        }
+    }
+}
 
 /// The red, green, and blue components of a color.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub(crate) struct ColorTriplet {
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ColorTriplet {
     /// Red component of the color.
-    pub(crate) red: u8,
+    pub red: u8,
     /// Green component of the color.
-    pub(crate) green: u8,
+    pub green: u8,
     /// Blue component of the color.
-    pub(crate) blue: u8,
+    pub blue: u8,
 }
 
 impl ColorTriplet {
@@ -19,25 +75,576 @@ impl ColorTriplet {
     /// * `red` - Red component of the color.
     /// * `green` - Green component of the color.
     /// * `blue` - Blue component of the color.
-    pub(crate) const fn new(red: u8, green: u8, blue: u8) -> Self { Self { red, green, blue } }
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self { Self { red, green, blue } }
 
     /// Get the color triplet in CSS style.
-    pub(crate) fn hex(&self) -> String {
-        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
-    }
+    pub fn hex(&self) -> String { format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue) }
 
     /// Get the color triplet in RBG format.
-    pub(crate) fn rgb(&self) -> String { format!("rgb({},{},{})", self.red, self.green, self.blue) }
+    pub fn rgb(&self) -> String { format!("rgb({},{},{})", self.red, self.green, self.blue) }
 
     /// Convert components into floats between 0 and 1.
     #[must_use]
-    pub(crate) fn normalized(&self) -> (f32, f32, f32) {
+    pub fn normalized(&self) -> (f32, f32, f32) {
         (
             f32::from(self.red) / 255.0,
             f32::from(self.green) / 255.0,
             f32::from(self.blue) / 255.0,
         )
     }
+
+    /// Get the distance to another color.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The color to compare with.
+    /// * `metric` - How to measure the distance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::{ColorDistance, ColorTriplet};
+    ///
+    /// let red = ColorTriplet::new(255, 0, 0);
+    ///
+    /// assert_eq!(red.distance(&red, ColorDistance::Ciede2000), 0.0);
+    /// assert!(red.distance(&ColorTriplet::new(0, 0, 255), ColorDistance::Oklab) > 0.5);
+    /// ```
+    pub fn distance(&self, other: &Self, metric: ColorDistance) -> f64 {
+        metric.between(metric.project(self), metric.project(other))
+    }
+
+    /// Get the relative luminance of the color, as defined by WCAG 2.
+    ///
+    /// # Returns
+    ///
+    /// The luminance, between 0 for black and 1 for white.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// assert_eq!(ColorTriplet::new(0, 0, 0).luminance(), 0.0);
+    /// assert_eq!(ColorTriplet::new(255, 255, 255).luminance(), 1.0);
+    /// ```
+    pub fn luminance(&self) -> f64 {
+        let (red, green, blue) = self.linear();
+
+        0.2126 * red + 0.7152 * green + 0.0722 * blue
+    }
+
+    /// Get the WCAG 2 contrast ratio between two colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The color to compare with.
+    ///
+    /// # Returns
+    ///
+    /// The contrast ratio, between 1 (no contrast) and 21 (black and white).
+    /// The order of the colors doesn't matter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// let black = ColorTriplet::new(0, 0, 0);
+    /// let white = ColorTriplet::new(255, 255, 255);
+    ///
+    /// assert_eq!(black.contrast_ratio(&white), 21.0);
+    /// assert_eq!(white.contrast_ratio(&white), 1.0);
+    /// ```
+    pub fn contrast_ratio(&self, other: &Self) -> f64 {
+        let (luminance1, luminance2) = (self.luminance(), other.luminance());
+
+        (luminance1.max(luminance2) + 0.05) / (luminance1.min(luminance2) + 0.05)
+    }
+
+    /// Get the components with the sRGB gamma removed, between 0 and 1.
+    fn linear(&self) -> (f64, f64, f64) {
+        let linear = |component: u8| {
+            let component = f64::from(component) / 255.0;
+
+            if component <= 0.040_45 {
+                component / 12.92
+            } else {
+                ((component + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        (linear(self.red), linear(self.green), linear(self.blue))
+    }
+
+    /// Create a color triplet from linear RGB components, clamping them to the
+    /// sRGB gamut.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn from_linear(red: f64, green: f64, blue: f64) -> Self {
+        let component = |component: f64| {
+            let component = if component <= 0.003_130_8 {
+                component * 12.92
+            } else {
+                1.055 * component.powf(1.0 / 2.4) - 0.055
+            };
+
+            (component.clamp(0.0, 1.0) * 255.0).round() as u8
+        };
+
+        Self::new(component(red), component(green), component(blue))
+    }
+
+    /// Create a color triplet from components between 0 and 1, clamping them.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn from_unit(red: f64, green: f64, blue: f64) -> Self {
+        let component = |component: f64| (component.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        Self::new(component(red), component(green), component(blue))
+    }
+
+    /// Get the components as floats between 0 and 1.
+    fn unit(self) -> (f64, f64, f64) {
+        (
+            f64::from(self.red) / 255.0,
+            f64::from(self.green) / 255.0,
+            f64::from(self.blue) / 255.0,
+        )
+    }
+
+    /// Convert to HSL.
+    ///
+    /// # Returns
+    ///
+    /// The hue in degrees between 0 and 360, and the saturation and lightness
+    /// between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// assert_eq!(ColorTriplet::new(255, 0, 0).to_hsl(), (0.0, 1.0, 0.5));
+    /// ```
+    pub fn to_hsl(self) -> (f64, f64, f64) {
+        let (red, green, blue) = self.unit();
+        let (max, min) = (red.max(green).max(blue), red.min(green).min(blue));
+        let lightness = (max + min) / 2.0;
+
+        if max == min {
+            return (0.0, 0.0, lightness);
+        }
+        let saturation = (max - min) / (1.0 - (2.0 * lightness - 1.0).abs());
+
+        (hue(red, green, blue), saturation, lightness)
+    }
+
+    /// Create a color triplet from HSL.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue` - The hue in degrees.
+    /// * `saturation` - The saturation, between 0 and 1.
+    /// * `lightness` - The lightness, between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// assert_eq!(
+    ///     ColorTriplet::from_hsl(120.0, 1.0, 0.25),
+    ///     ColorTriplet::new(0, 128, 0)
+    /// );
+    /// ```
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let (saturation, lightness) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let (red, green, blue) = from_hue(hue, chroma);
+        let offset = lightness - chroma / 2.0;
+
+        Self::from_unit(red + offset, green + offset, blue + offset)
+    }
+
+    /// Convert to HSV.
+    ///
+    /// # Returns
+    ///
+    /// The hue in degrees between 0 and 360, and the saturation and value
+    /// between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// assert_eq!(ColorTriplet::new(0, 0, 255).to_hsv(), (240.0, 1.0, 1.0));
+    /// ```
+    pub fn to_hsv(self) -> (f64, f64, f64) {
+        let (red, green, blue) = self.unit();
+        let (max, min) = (red.max(green).max(blue), red.min(green).min(blue));
+
+        if max == min {
+            return (0.0, 0.0, max);
+        }
+
+        (hue(red, green, blue), (max - min) / max, max)
+    }
+
+    /// Create a color triplet from HSV.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue` - The hue in degrees.
+    /// * `saturation` - The saturation, between 0 and 1.
+    /// * `value` - The value, between 0 and 1.
+    pub fn from_hsv(hue: f64, saturation: f64, value: f64) -> Self {
+        let (saturation, value) = (saturation.clamp(0.0, 1.0), value.clamp(0.0, 1.0));
+        let chroma = value * saturation;
+        let (red, green, blue) = from_hue(hue, chroma);
+        let offset = value - chroma;
+
+        Self::from_unit(red + offset, green + offset, blue + offset)
+    }
+
+    /// Convert to CIE L\*a\*b\* under the D65 white point.
+    ///
+    /// # Returns
+    ///
+    /// The lightness between 0 and 100, and the a\* and b\* components.
+    pub fn to_lab(self) -> (f64, f64, f64) {
+        let (red, green, blue) = self.linear();
+        let x = (0.412_456_4 * red + 0.357_576_1 * green + 0.180_437_5 * blue) / 0.950_47;
+        let y = 0.212_672_9 * red + 0.715_152_2 * green + 0.072_175 * blue;
+        let z = (0.019_333_9 * red + 0.119_192 * green + 0.950_304_1 * blue) / 1.088_83;
+
+        let f = |t: f64| {
+            if t > 216.0 / 24_389.0 {
+                t.cbrt()
+            } else {
+                t * 841.0 / 108.0 + 4.0 / 29.0
+            }
+        };
+        let (x, y, z) = (f(x), f(y), f(z));
+
+        (116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z))
+    }
+
+    /// Create a color triplet from CIE L\*a\*b\* under the D65 white point,
+    /// clamping colors outside of the sRGB gamut.
+    ///
+    /// # Arguments
+    ///
+    /// * `lightness` - The lightness, between 0 and 100.
+    /// * `a` - The green to red component.
+    /// * `b` - The blue to yellow component.
+    pub fn from_lab(lightness: f64, a: f64, b: f64) -> Self {
+        let y = (lightness + 16.0) / 116.0;
+        let (x, z) = (y + a / 500.0, y - b / 200.0);
+
+        let f = |t: f64| {
+            if t > 6.0 / 29.0 {
+                t.powi(3)
+            } else {
+                (t - 4.0 / 29.0) * 108.0 / 841.0
+            }
+        };
+        let (x, y, z) = (f(x) * 0.950_47, f(y), f(z) * 1.088_83);
+
+        Self::from_linear(
+            3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+            -0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z,
+            0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+        )
+    }
+
+    /// Convert to OKLab.
+    ///
+    /// # Returns
+    ///
+    /// The lightness between 0 and 1, and the a and b components.
+    pub fn to_oklab(self) -> (f64, f64, f64) {
+        let (red, green, blue) = self.linear();
+        let l = (0.412_221_470_8 * red + 0.536_332_536_3 * green + 0.051_445_992_9 * blue).cbrt();
+        let m = (0.211_903_498_2 * red + 0.680_699_545_1 * green + 0.107_396_956_6 * blue).cbrt();
+        let s = (0.088_302_461_9 * red + 0.281_718_837_6 * green + 0.629_978_700_5 * blue).cbrt();
+
+        (
+            0.210_454_255_3 * l + 0.793_617_785 * m - 0.004_072_046_8 * s,
+            1.977_998_495_1 * l - 2.428_592_205 * m + 0.450_593_709_9 * s,
+            0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766 * s,
+        )
+    }
+
+    /// Create a color triplet from OKLab, clamping colors outside of the sRGB
+    /// gamut.
+    ///
+    /// # Arguments
+    ///
+    /// * `lightness` - The lightness, between 0 and 1.
+    /// * `a` - The green to red component.
+    /// * `b` - The blue to yellow component.
+    pub fn from_oklab(lightness: f64, a: f64, b: f64) -> Self {
+        let l = (lightness + 0.396_337_777_4 * a + 0.215_803_757_3 * b).powi(3);
+        let m = (lightness - 0.105_561_345_8 * a - 0.063_854_172_8 * b).powi(3);
+        let s = (lightness - 0.089_484_177_5 * a - 1.291_485_548 * b).powi(3);
+
+        Self::from_linear(
+            4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s,
+            -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s,
+            -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701 * s,
+        )
+    }
+
+    /// Convert to OKLCH, the polar form of OKLab.
+    ///
+    /// # Returns
+    ///
+    /// The lightness between 0 and 1, the chroma, and the hue in degrees
+    /// between 0 and 360.
+    pub fn to_oklch(self) -> (f64, f64, f64) {
+        let (lightness, a, b) = self.to_oklab();
+        // Grays have no hue, but rounding leaves tiny a and b components.
+        let chroma = a.hypot(b);
+        let hue = if chroma < 1e-4 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        };
+
+        (lightness, chroma, hue)
+    }
+
+    /// Create a color triplet from OKLCH, clamping colors outside of the sRGB
+    /// gamut.
+    ///
+    /// # Arguments
+    ///
+    /// * `lightness` - The lightness, between 0 and 1.
+    /// * `chroma` - The chroma.
+    /// * `hue` - The hue in degrees.
+    pub fn from_oklch(lightness: f64, chroma: f64, hue: f64) -> Self {
+        let (sin, cos) = hue.to_radians().sin_cos();
+
+        Self::from_oklab(lightness, chroma * cos, chroma * sin)
+    }
+
+    /// Make the color lighter by increasing its HSL lightness.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount to add to the lightness, between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// assert_eq!(
+    ///     ColorTriplet::new(0, 128, 0).lighten(0.25),
+    ///     ColorTriplet::new(0, 255, 0)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn lighten(&self, amount: f64) -> Self {
+        let (hue, saturation, lightness) = self.to_hsl();
+
+        Self::from_hsl(hue, saturation, lightness + amount)
+    }
+
+    /// Make the color darker by decreasing its HSL lightness.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount to remove from the lightness, between 0 and 1.
+    #[must_use]
+    pub fn darken(&self, amount: f64) -> Self { self.lighten(-amount) }
+
+    /// Make the color more vivid by increasing its HSL saturation.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount to add to the saturation, between 0 and 1.
+    #[must_use]
+    pub fn saturate(&self, amount: f64) -> Self {
+        let (hue, saturation, lightness) = self.to_hsl();
+
+        Self::from_hsl(hue, saturation + amount, lightness)
+    }
+
+    /// Make the color duller by decreasing its HSL saturation.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount to remove from the saturation, between 0 and 1.
+    #[must_use]
+    pub fn desaturate(&self, amount: f64) -> Self { self.saturate(-amount) }
+
+    /// Blend with another color in RGB, as Rich's `blend_rgb` does.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The color to blend with.
+    /// * `cross_fade` - How much of `other` to mix in, between 0 (none) and 1
+    ///   (only `other`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// let black = ColorTriplet::new(0, 0, 0);
+    /// let white = ColorTriplet::new(255, 255, 255);
+    ///
+    /// assert_eq!(black.blend(&white, 0.5), ColorTriplet::new(127, 127, 127));
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn blend(&self, other: &Self, cross_fade: f64) -> Self {
+        let cross_fade = cross_fade.clamp(0.0, 1.0);
+        let blend = |component1: u8, component2: u8| {
+            let (component1, component2) = (f64::from(component1), f64::from(component2));
+
+            (component1 + (component2 - component1) * cross_fade) as u8
+        };
+
+        Self::new(
+            blend(self.red, other.red),
+            blend(self.green, other.green),
+            blend(self.blue, other.blue),
+        )
+    }
+
+    /// Mix with another color in OKLab, so that the midpoint of two colors
+    /// looks halfway between them.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The color to mix with.
+    /// * `weight` - How much of `other` to mix in, between 0 (none) and 1 (only
+    ///   `other`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    ///
+    /// let black = ColorTriplet::new(0, 0, 0);
+    /// let white = ColorTriplet::new(255, 255, 255);
+    ///
+    /// assert_eq!(black.mix(&white, 0.0), black);
+    /// assert_eq!(black.mix(&white, 0.5), ColorTriplet::new(99, 99, 99));
+    /// ```
+    #[must_use]
+    pub fn mix(&self, other: &Self, weight: f64) -> Self {
+        let weight = weight.clamp(0.0, 1.0);
+        let (l1, a1, b1) = self.to_oklab();
+        let (l2, a2, b2) = other.to_oklab();
+
+        Self::from_oklab(
+            l1 + (l2 - l1) * weight,
+            a1 + (a2 - a1) * weight,
+            b1 + (b2 - b1) * weight,
+        )
+    }
+}
+
+/// Get the hue in degrees of RGB components between 0 and 1, which must not
+/// all be equal.
+fn hue(red: f64, green: f64, blue: f64) -> f64 {
+    let (max, min) = (red.max(green).max(blue), red.min(green).min(blue));
+    let delta = max - min;
+
+    let hue = if max == red {
+        (green - blue) / delta
+    } else if max == green {
+        (blue - red) / delta + 2.0
+    } else {
+        (red - green) / delta + 4.0
+    };
+
+    (hue * 60.0).rem_euclid(360.0)
+}
+
+/// Get RGB components for a hue in degrees and a chroma, before adding the
+/// offset that sets the lightness or value.
+fn from_hue(hue: f64, chroma: f64) -> (f64, f64, f64) {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    match sector {
+        sector if sector < 1.0 => (chroma, x, 0.0),
+        sector if sector < 2.0 => (x, chroma, 0.0),
+        sector if sector < 3.0 => (0.0, chroma, x),
+        sector if sector < 4.0 => (0.0, x, chroma),
+        sector if sector < 5.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    }
+}
+
+/// Get the CIEDE2000 color difference between two L\*a\*b\* colors.
+#[allow(clippy::similar_names)]
+fn ciede2000(lab1: (f64, f64, f64), lab2: (f64, f64, f64)) -> f64 {
+    let (l1, a1, b1) = lab1;
+    let (l2, a2, b2) = lab2;
+    let pow25_7 = 25.0_f64.powi(7);
+
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt());
+    let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 <= h1 {
+        h2 - h1 + 360.0
+    } else {
+        h2 - h1 - 360.0
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt();
+    let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    ((delta_l / s_l).powi(2)
+        + (delta_c / s_c).powi(2)
+        + (delta_h / s_h).powi(2)
+        + r_t * (delta_c / s_c) * (delta_h / s_h))
+        .sqrt()
 }
 
 #[cfg(test)]
@@ -66,4 +673,205 @@ mod tests {
     fn test_normalized(#[case] color_triplet: &ColorTriplet, #[case] result: (f32, f32, f32)) {
         assert_eq!(color_triplet.normalized(), result);
     }
+
+    #[rstest]
+    #[case(ColorTriplet::new(0, 0, 0), (0.0, 0.0, 0.0))]
+    #[case(ColorTriplet::new(255, 255, 255), (100.0, 0.0, 0.0))]
+    #[case(ColorTriplet::new(255, 0, 0), (53.2408, 80.0925, 67.2032))]
+    #[case(ColorTriplet::new(0, 0, 255), (32.2970, 79.1875, -107.8602))]
+    fn test_to_lab(#[case] color_triplet: ColorTriplet, #[case] result: (f64, f64, f64)) {
+        let lab = color_triplet.to_lab();
+
+        assert!((lab.0 - result.0).abs() < 1e-3, "{lab:?}");
+        assert!((lab.1 - result.1).abs() < 1e-3, "{lab:?}");
+        assert!((lab.2 - result.2).abs() < 1e-3, "{lab:?}");
+    }
+
+    #[rstest]
+    #[case(ColorTriplet::new(255, 255, 255), (1.0, 0.0, 0.0))]
+    #[case(ColorTriplet::new(255, 0, 0), (0.627_955, 0.224_863, 0.125_846))]
+    fn test_to_oklab(#[case] color_triplet: ColorTriplet, #[case] result: (f64, f64, f64)) {
+        let oklab = color_triplet.to_oklab();
+
+        assert!((oklab.0 - result.0).abs() < 1e-4, "{oklab:?}");
+        assert!((oklab.1 - result.1).abs() < 1e-4, "{oklab:?}");
+        assert!((oklab.2 - result.2).abs() < 1e-4, "{oklab:?}");
+    }
+
+    // Test data from Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference
+    // Formula".
+    #[rstest]
+    #[case((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425)]
+    #[case((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669)]
+    #[case((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492)]
+    #[case((50.0, 2.5, 0.0), (50.0, 0.0, -2.5), 4.3065)]
+    #[case((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644)]
+    #[case((22.7233, 20.0904, -46.694), (23.0331, 14.973, -42.5619), 2.0373)]
+    fn test_ciede2000(
+        #[case] lab1: (f64, f64, f64),
+        #[case] lab2: (f64, f64, f64),
+        #[case] result: f64,
+    ) {
+        assert!((ciede2000(lab1, lab2) - result).abs() < 1e-4);
+        assert!((ciede2000(lab2, lab1) - result).abs() < 1e-4);
+    }
+
+    #[rstest]
+    #[case(ColorDistance::Redmean, 764.833_3)]
+    #[case(ColorDistance::Cie76, 100.0)]
+    #[case(ColorDistance::Ciede2000, 100.0)]
+    #[case(ColorDistance::Oklab, 1.0)]
+    fn test_distance(#[case] metric: ColorDistance, #[case] result: f64) {
+        let black = ColorTriplet::new(0, 0, 0);
+        let white = ColorTriplet::new(255, 255, 255);
+
+        assert_eq!(black.distance(&black, metric), 0.0);
+        assert!((black.distance(&white, metric) - result).abs() < 1e-3);
+    }
+
+    #[rstest]
+    #[case(ColorTriplet::new(0, 0, 0), (0.0, 0.0, 0.0))]
+    #[case(ColorTriplet::new(255, 255, 255), (0.0, 0.0, 1.0))]
+    #[case(ColorTriplet::new(255, 0, 0), (0.0, 1.0, 0.5))]
+    #[case(ColorTriplet::new(0, 255, 255), (180.0, 1.0, 0.5))]
+    #[case(ColorTriplet::new(255, 0, 255), (300.0, 1.0, 0.5))]
+    #[case(ColorTriplet::new(102, 51, 153), (270.0, 0.5, 0.4))]
+    fn test_to_hsl(#[case] color_triplet: ColorTriplet, #[case] result: (f64, f64, f64)) {
+        let hsl = color_triplet.to_hsl();
+
+        assert!((hsl.0 - result.0).abs() < 1e-9, "{hsl:?}");
+        assert!((hsl.1 - result.1).abs() < 1e-9, "{hsl:?}");
+        assert!((hsl.2 - result.2).abs() < 1e-9, "{hsl:?}");
+    }
+
+    #[rstest]
+    #[case(ColorTriplet::new(0, 0, 0), (0.0, 0.0, 0.0))]
+    #[case(ColorTriplet::new(255, 255, 0), (60.0, 1.0, 1.0))]
+    #[case(ColorTriplet::new(128, 64, 64), (0.0, 0.5, 128.0 / 255.0))]
+    fn test_to_hsv(#[case] color_triplet: ColorTriplet, #[case] result: (f64, f64, f64)) {
+        let hsv = color_triplet.to_hsv();
+
+        assert!((hsv.0 - result.0).abs() < 1e-9, "{hsv:?}");
+        assert!((hsv.1 - result.1).abs() < 1e-9, "{hsv:?}");
+        assert!((hsv.2 - result.2).abs() < 1e-9, "{hsv:?}");
+    }
+
+    #[rstest]
+    #[case(ColorTriplet::new(0, 0, 0))]
+    #[case(ColorTriplet::new(255, 255, 255))]
+    #[case(ColorTriplet::new(128, 128, 128))]
+    #[case(ColorTriplet::new(255, 0, 0))]
+    #[case(ColorTriplet::new(12, 200, 99))]
+    #[case(ColorTriplet::new(250, 5, 190))]
+    #[case(ColorTriplet::new(1, 2, 3))]
+    fn test_round_trip(#[case] color_triplet: ColorTriplet) {
+        let (hue, saturation, lightness) = color_triplet.to_hsl();
+        assert_eq!(
+            ColorTriplet::from_hsl(hue, saturation, lightness),
+            color_triplet
+        );
+
+        let (hue, saturation, value) = color_triplet.to_hsv();
+        assert_eq!(
+            ColorTriplet::from_hsv(hue, saturation, value),
+            color_triplet
+        );
+
+        let (lightness, a, b) = color_triplet.to_lab();
+        assert_eq!(ColorTriplet::from_lab(lightness, a, b), color_triplet);
+
+        let (lightness, a, b) = color_triplet.to_oklab();
+        assert_eq!(ColorTriplet::from_oklab(lightness, a, b), color_triplet);
+
+        let (lightness, chroma, hue) = color_triplet.to_oklch();
+        assert_eq!(
+            ColorTriplet::from_oklch(lightness, chroma, hue),
+            color_triplet
+        );
+    }
+
+    #[test]
+    fn test_to_oklch() {
+        let (lightness, chroma, hue) = ColorTriplet::new(255, 0, 0).to_oklch();
+
+        assert!((lightness - 0.627_955).abs() < 1e-4);
+        assert!((chroma - 0.257_683).abs() < 1e-4);
+        assert!((hue - 29.2339).abs() < 1e-3);
+        assert_eq!(ColorTriplet::new(128, 128, 128).to_oklch().2, 0.0);
+    }
+
+    #[rstest]
+    #[case(
+        ColorTriplet::from_lab(100.0, 100.0, 100.0),
+        ColorTriplet::new(255, 147, 49)
+    )]
+    #[case(
+        ColorTriplet::from_oklch(0.9, 0.4, 150.0),
+        ColorTriplet::new(0, 255, 0)
+    )]
+    #[case(ColorTriplet::from_hsl(-120.0, 2.0, 0.5), ColorTriplet::new(0, 0, 255))]
+    fn test_out_of_gamut(#[case] color_triplet: ColorTriplet, #[case] result: ColorTriplet) {
+        assert_eq!(color_triplet, result);
+    }
+
+    #[rstest]
+    #[case(ColorTriplet::new(0, 128, 0).lighten(0.25), ColorTriplet::new(0, 255, 0))]
+    #[case(ColorTriplet::new(0, 255, 0).darken(0.25), ColorTriplet::new(0, 128, 0))]
+    #[case(ColorTriplet::new(255, 255, 255).lighten(0.5), ColorTriplet::new(255, 255, 255))]
+    #[case(ColorTriplet::new(191, 64, 64).saturate(0.5), ColorTriplet::new(255, 0, 0))]
+    #[case(ColorTriplet::new(255, 0, 0).desaturate(1.0), ColorTriplet::new(128, 128, 128))]
+    fn test_adjust(#[case] color_triplet: ColorTriplet, #[case] result: ColorTriplet) {
+        assert_eq!(color_triplet, result);
+    }
+
+    #[rstest]
+    #[case(ColorTriplet::new(0, 0, 0), 0.0)]
+    #[case(ColorTriplet::new(255, 0, 0), 0.2126)]
+    #[case(ColorTriplet::new(0, 255, 0), 0.7152)]
+    #[case(ColorTriplet::new(128, 128, 128), 0.215_861)]
+    fn test_luminance(#[case] color_triplet: ColorTriplet, #[case] result: f64) {
+        assert!((color_triplet.luminance() - result).abs() < 1e-6);
+    }
+
+    #[rstest]
+    #[case(ColorTriplet::new(255, 255, 255), ColorTriplet::new(0, 0, 255), 8.592)]
+    #[case(
+        ColorTriplet::new(118, 118, 118),
+        ColorTriplet::new(255, 255, 255),
+        4.542
+    )]
+    #[case(ColorTriplet::new(255, 0, 0), ColorTriplet::new(0, 0, 0), 5.252)]
+    fn test_contrast_ratio(
+        #[case] color1: ColorTriplet,
+        #[case] color2: ColorTriplet,
+        #[case] result: f64,
+    ) {
+        assert!((color1.contrast_ratio(&color2) - result).abs() < 1e-3);
+        assert!((color2.contrast_ratio(&color1) - result).abs() < 1e-3);
+    }
+
+    #[rstest]
+    #[case(0.0, ColorTriplet::new(255, 0, 0))]
+    #[case(1.0, ColorTriplet::new(0, 0, 255))]
+    #[case(-1.0, ColorTriplet::new(255, 0, 0))]
+    #[case(0.5, ColorTriplet::new(127, 0, 127))]
+    #[case(0.25, ColorTriplet::new(191, 0, 63))]
+    fn test_blend(#[case] cross_fade: f64, #[case] result: ColorTriplet) {
+        assert_eq!(
+            ColorTriplet::new(255, 0, 0).blend(&ColorTriplet::new(0, 0, 255), cross_fade),
+            result
+        );
+    }
+
+    #[rstest]
+    #[case(0.0, ColorTriplet::new(255, 0, 0))]
+    #[case(1.0, ColorTriplet::new(0, 0, 255))]
+    #[case(2.0, ColorTriplet::new(0, 0, 255))]
+    #[case(0.5, ColorTriplet::new(140, 83, 162))]
+    fn test_mix(#[case] weight: f64, #[case] result: ColorTriplet) {
+        assert_eq!(
+            ColorTriplet::new(255, 0, 0).mix(&ColorTriplet::new(0, 0, 255), weight),
+            result
+        );
+    }
 }
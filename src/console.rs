@@ -0,0 +1,1540 @@
+use std::collections::HashMap;
+use std::fs;
+use std::hash::BuildHasher;
+use std::io::{self, IsTerminal, Write};
+use std::panic::Location;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use terminal_size::{terminal_size, Height, Width};
+
+use crate::cells::cell_len;
+use crate::color::ColorSystem;
+use crate::export_format::{
+    adler32, escape_html, format_svg_number, make_svg_tag, render_format, _SVG_CLASSES_PREFIX,
+    CONSOLE_HTML_FORMAT, CONSOLE_SVG_FORMAT,
+};
+use crate::measure::Measurement;
+use crate::rule::Rule;
+use crate::segment::Segment;
+use crate::style::Style;
+use crate::terminal_theme::{TerminalTheme, DEFAULT_TERMINAL_THEME, SVG_EXPORT_THEME};
+
+/// How text is aligned within the available width.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum JustifyMethod {
+    /// Use the default justification of the renderable.
+    #[default]
+    Default,
+    /// Align text to the left.
+    Left,
+    /// Center text.
+    Center,
+    /// Align text to the right.
+    Right,
+    /// Pad spaces between words so that text fills the width.
+    Full,
+}
+
+/// What happens to text that doesn't fit in the available width.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum OverflowMethod {
+    /// Fold the text on to the next line.
+    #[default]
+    Fold,
+    /// Crop the text at the end of the line.
+    Crop,
+    /// Crop the text and replace the last character with "…".
+    Ellipsis,
+    /// Leave the text as is.
+    Ignore,
+}
+
+/// Size of the terminal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ConsoleDimensions {
+    /// The width of the console in cells.
+    pub width: u32,
+    /// The height of the console in lines.
+    pub height: u32,
+}
+
+/// Options for the render process.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ConsoleOptions {
+    /// Size of the console.
+    pub size: ConsoleDimensions,
+    /// Minimum width of renderable.
+    pub min_width: u32,
+    /// Maximum width of renderable.
+    pub max_width: u32,
+    /// `true` if the target is a terminal, otherwise `false`.
+    pub is_terminal: bool,
+    /// Height of container (starts as terminal height).
+    pub max_height: u32,
+    /// Justify value override for the renderable.
+    pub justify: Option<JustifyMethod>,
+    /// Overflow value override for the renderable.
+    pub overflow: Option<OverflowMethod>,
+    /// Disable wrapping for text.
+    pub no_wrap: bool,
+    /// Height available, or `None` for no height limit.
+    pub height: Option<u32>,
+}
+
+impl ConsoleOptions {
+    /// Update just the width, and return a copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - New width (sets both `min_width` and `max_width`).
+    #[must_use]
+    pub fn update_width(&self, width: u32) -> Self {
+        Self {
+            min_width: width,
+            max_width: width,
+            ..self.clone()
+        }
+    }
+
+    /// Update the height, and return a copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - New height.
+    #[must_use]
+    pub fn update_height(&self, height: u32) -> Self {
+        Self {
+            max_height: height,
+            height: Some(height),
+            ..self.clone()
+        }
+    }
+
+    /// Return a copy of the options with height set to `None`.
+    #[must_use]
+    pub fn reset_height(&self) -> Self {
+        Self {
+            height: None,
+            ..self.clone()
+        }
+    }
+
+    /// Update the width and height, and return a copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - New width (sets both `min_width` and `max_width`).
+    /// * `height` - New height.
+    #[must_use]
+    pub fn update_dimensions(&self, width: u32, height: u32) -> Self {
+        self.update_width(width).update_height(height)
+    }
+}
+
+/// An object that may be rendered by a [`Console`].
+pub trait Renderable {
+    /// Render the object in to segments.
+    ///
+    /// # Arguments
+    ///
+    /// * `console` - The console that is rendering the object.
+    /// * `options` - Options for the render process.
+    fn render(&self, console: &Console<'_>, options: &ConsoleOptions) -> Vec<Segment>;
+
+    /// Get the minimum and maximum number of cells required to render the
+    /// object.
+    ///
+    /// Returns `None` by default, in which case the object may take up all of
+    /// the available width. Use [`Measurement::get`] rather than calling this
+    /// directly, as it normalizes and clamps the measurement.
+    ///
+    /// # Arguments
+    ///
+    /// * `console` - The console that is measuring the object.
+    /// * `options` - Options for the render process.
+    fn measure(&self, _console: &Console<'_>, _options: &ConsoleOptions) -> Option<Measurement> {
+        None
+    }
+}
+
+impl<T: Renderable + ?Sized> Renderable for &T {
+    fn render(&self, console: &Console<'_>, options: &ConsoleOptions) -> Vec<Segment> {
+        (**self).render(console, options)
+    }
+
+    fn measure(&self, console: &Console<'_>, options: &ConsoleOptions) -> Option<Measurement> {
+        (**self).measure(console, options)
+    }
+}
+
+impl Renderable for str {
+    fn render(&self, _console: &Console<'_>, _options: &ConsoleOptions) -> Vec<Segment> {
+        vec![Segment::new(self, None), Segment::line()]
+    }
+
+    fn measure(&self, _console: &Console<'_>, _options: &ConsoleOptions) -> Option<Measurement> {
+        let maximum = self.lines().map(cell_len).max().unwrap_or(0);
+        let minimum = self.split_whitespace().map(cell_len).max().unwrap_or(0);
+
+        Some(Measurement::new(minimum, maximum))
+    }
+}
+
+impl Renderable for String {
+    fn render(&self, console: &Console<'_>, options: &ConsoleOptions) -> Vec<Segment> {
+        self.as_str().render(console, options)
+    }
+
+    fn measure(&self, console: &Console<'_>, options: &ConsoleOptions) -> Option<Measurement> {
+        self.as_str().measure(console, options)
+    }
+}
+
+/// How a [`Console`] picks its color system.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ColorSystemSetting {
+    /// Detect the color system from the environment when it's needed.
+    Auto,
+    /// Use the color system given, or `None` for no color.
+    Fixed(Option<ColorSystem>),
+}
+
+/// A high level console interface.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::console::Console;
+///
+/// let mut output = Vec::new();
+/// let mut console = Console::new().with_file(&mut output).with_width(20);
+///
+/// console.print("Hello, World!").unwrap();
+/// drop(console);
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), "Hello, World!\n");
+/// ```
+pub struct Console<'a> {
+    file: Box<dyn Write + 'a>,
+    color_system: ColorSystemSetting,
+    is_terminal: bool,
+    width: Option<u32>,
+    height: Option<u32>,
+    environ: HashMap<String, String>,
+    log_time: bool,
+    log_path: bool,
+    record: bool,
+    /// Segments written while recording, for the export methods.
+    record_buffer: Vec<Segment>,
+}
+
+impl Default for Console<'_> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<'a> Console<'a> {
+    /// Instantiate a new [`Console`] writing to stdout.
+    pub fn new() -> Self {
+        Self {
+            file: Box::new(io::stdout()),
+            color_system: ColorSystemSetting::Auto,
+            is_terminal: io::stdout().is_terminal(),
+            width: None,
+            height: None,
+            environ: std::env::vars().collect(),
+            log_time: true,
+            log_path: true,
+            record: false,
+            record_buffer: vec![],
+        }
+    }
+
+    /// Write to `file` instead of stdout.
+    ///
+    /// The console is no longer considered a terminal, unless
+    /// [`Console::with_force_terminal`] is called afterwards.
+    #[must_use]
+    pub fn with_file(self, file: impl Write + 'a) -> Self {
+        Self {
+            file: Box::new(file),
+            is_terminal: false,
+            ..self
+        }
+    }
+
+    /// Set the color system, or `None` to disable color.
+    #[must_use]
+    pub fn with_color_system(self, color_system: Option<ColorSystem>) -> Self {
+        Self {
+            color_system: ColorSystemSetting::Fixed(color_system),
+            ..self
+        }
+    }
+
+    /// Treat the output as a terminal (or not) regardless of auto-detection.
+    #[must_use]
+    pub fn with_force_terminal(self, force_terminal: bool) -> Self {
+        Self {
+            is_terminal: force_terminal,
+            ..self
+        }
+    }
+
+    /// Set the width of the console instead of auto-detecting it.
+    #[must_use]
+    pub fn with_width(self, width: u32) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    /// Set the height of the console instead of auto-detecting it.
+    #[must_use]
+    pub fn with_height(self, height: u32) -> Self {
+        Self {
+            height: Some(height),
+            ..self
+        }
+    }
+
+    /// Use `environ` instead of the process environment.
+    #[must_use]
+    pub fn with_environ(self, environ: HashMap<String, String>) -> Self { Self { environ, ..self } }
+
+    /// Enable or disable the time and path columns of [`Console::log`].
+    #[must_use]
+    pub fn with_log_columns(self, log_time: bool, log_path: bool) -> Self {
+        Self {
+            log_time,
+            log_path,
+            ..self
+        }
+    }
+
+    /// Record everything written to the console, so it can be exported with
+    /// [`Console::export_text`], [`Console::export_html`] or
+    /// [`Console::export_svg`].
+    #[must_use]
+    pub fn with_record(self, record: bool) -> Self { Self { record, ..self } }
+
+    /// Get the color system used to render styles, or `None` for no color.
+    ///
+    /// Unless it was set with [`Console::with_color_system`], the color system
+    /// is detected from the environment with [`detect_color_system`].
+    pub fn color_system(&self) -> Option<ColorSystem> {
+        match self.color_system {
+            ColorSystemSetting::Auto => detect_color_system(&self.environ, self.is_terminal),
+            ColorSystemSetting::Fixed(color_system) => color_system,
+        }
+    }
+
+    /// Check if the console is writing to a terminal.
+    pub const fn is_terminal(&self) -> bool { self.is_terminal }
+
+    /// Get the size of the console.
+    ///
+    /// Explicit sizes take precedence, followed by the `COLUMNS` and `LINES`
+    /// environment variables, the size of the terminal, and finally 80 by 25.
+    pub fn size(&self) -> ConsoleDimensions {
+        let from_environ = |key: &str| {
+            self.environ
+                .get(key)
+                .and_then(|value| value.trim().parse::<u32>().ok())
+        };
+
+        let width = self.width.or_else(|| from_environ("COLUMNS"));
+        let height = self.height.or_else(|| from_environ("LINES"));
+
+        // Only ask the terminal for the dimensions that are still missing.
+        let (terminal_width, terminal_height) = if width.is_none() || height.is_none() {
+            terminal_size().map_or((80, 25), |(Width(width), Height(height))| {
+                (u32::from(width), u32::from(height))
+            })
+        } else {
+            (80, 25)
+        };
+
+        ConsoleDimensions {
+            width: width.unwrap_or(terminal_width),
+            height: height.unwrap_or(terminal_height),
+        }
+    }
+
+    /// Get the width of the console.
+    pub fn width(&self) -> u32 { self.size().width }
+
+    /// Get the height of the console.
+    pub fn height(&self) -> u32 { self.size().height }
+
+    /// Get default console options.
+    pub fn options(&self) -> ConsoleOptions {
+        let size = self.size();
+
+        ConsoleOptions {
+            size,
+            min_width: 1,
+            max_width: size.width,
+            is_terminal: self.is_terminal,
+            max_height: size.height,
+            justify: None,
+            overflow: None,
+            no_wrap: false,
+            height: None,
+        }
+    }
+
+    /// Render an object in to an iterable of segments.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderable` - An object that can be rendered.
+    /// * `options` - Options for the render process, or `None` to use the
+    ///   console's default options.
+    pub fn render(
+        &self,
+        renderable: &(impl Renderable + ?Sized),
+        options: Option<&ConsoleOptions>,
+    ) -> Vec<Segment> {
+        match options {
+            Some(options) => renderable.render(self, options),
+            None => renderable.render(self, &self.options()),
+        }
+    }
+
+    /// Measure a renderable.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderable` - Any renderable.
+    /// * `options` - Options to use when measuring, or `None` to use the
+    ///   console's default options.
+    pub fn measure(
+        &self,
+        renderable: &(impl Renderable + ?Sized),
+        options: Option<&ConsoleOptions>,
+    ) -> Measurement {
+        match options {
+            Some(options) => Measurement::get(self, options, renderable),
+            None => Measurement::get(self, &self.options(), renderable),
+        }
+    }
+
+    /// Render objects in to a list of lines.
+    ///
+    /// The output of `render_lines` is useful when further formatting of
+    /// rendered console text is required, such as the `Panel` class which
+    /// draws a border around any renderable object.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderable` - Any object renderable in the console.
+    /// * `options` - Console options, or `None` to use the console's default
+    ///   options.
+    /// * `style` - Optional style to apply to renderables.
+    /// * `pad` - Pad lines shorter than the render width.
+    /// * `new_lines` - Include "\n" characters at the end of lines.
+    pub fn render_lines(
+        &self,
+        renderable: &(impl Renderable + ?Sized),
+        options: Option<&ConsoleOptions>,
+        style: Option<&Style>,
+        pad: bool,
+        new_lines: bool,
+    ) -> Vec<Vec<Segment>> {
+        let options = options.cloned().unwrap_or_else(|| self.options());
+        let segments = Segment::apply_style(&self.render(renderable, Some(&options)), style, None);
+        let mut lines =
+            Segment::split_and_crop_lines(&segments, options.max_width, style, pad, new_lines);
+
+        if let Some(height) = options.height {
+            lines = Segment::set_shape(&lines, options.max_width, Some(height), style, new_lines);
+        }
+
+        lines
+    }
+
+    /// Print a renderable to the console.
+    ///
+    /// Lines which are wider than the console are cropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderable` - The object to print.
+    ///
+    /// # Errors
+    ///
+    /// Any error from writing to the console's file.
+    pub fn print(&mut self, renderable: &(impl Renderable + ?Sized)) -> io::Result<()> {
+        self.print_styled(renderable, None)
+    }
+
+    /// Print a renderable to the console with a style applied to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderable` - The object to print.
+    /// * `style` - A style to apply to the output.
+    ///
+    /// # Errors
+    ///
+    /// Any error from writing to the console's file.
+    pub fn print_styled(
+        &mut self,
+        renderable: &(impl Renderable + ?Sized),
+        style: Option<&Style>,
+    ) -> io::Result<()> {
+        let options = self.options();
+        let segments = self.render(renderable, Some(&options));
+        let mut buffer = vec![];
+        for line in Segment::split_and_crop_lines(&segments, options.max_width, None, false, false)
+        {
+            buffer.extend(Segment::apply_style(&line, style, None));
+            buffer.push(Segment::line());
+        }
+
+        self.write_segments(&buffer)
+    }
+
+    /// Log a renderable to the console, with the time and the location of
+    /// the caller.
+    ///
+    /// The time is in UTC.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderable` - The object to log.
+    ///
+    /// # Errors
+    ///
+    /// Any error from writing to the console's file.
+    #[track_caller]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn log(&mut self, renderable: &(impl Renderable + ?Sized)) -> io::Result<()> {
+        let location = Location::caller();
+        let options = self.options();
+
+        let time = if self.log_time {
+            let seconds = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs() % 86400);
+
+            Some(format!(
+                "[{:02}:{:02}:{:02}] ",
+                seconds / 3600,
+                seconds % 3600 / 60,
+                seconds % 60
+            ))
+        } else {
+            None
+        };
+        let path = if self.log_path {
+            let file_name = Path::new(location.file())
+                .file_name()
+                .map_or_else(|| location.file().into(), |name| name.to_string_lossy());
+
+            Some(format!(" {file_name}:{}", location.line()))
+        } else {
+            None
+        };
+
+        let time_width = time.as_deref().map_or(0, cell_len);
+        let path_width = path.as_deref().map_or(0, cell_len);
+        let message_width = options
+            .max_width
+            .saturating_sub(time_width + path_width)
+            .max(1);
+
+        let lines = self.render_lines(
+            renderable,
+            Some(&options.update_width(message_width)),
+            None,
+            path.is_some(),
+            false,
+        );
+
+        let mut buffer = vec![];
+        for (index, line) in lines.into_iter().enumerate() {
+            if let Some(time) = &time {
+                buffer.push(if index == 0 {
+                    Segment::new(time, Some(Style::parse("cyan dim").unwrap()))
+                } else {
+                    Segment::new(" ".repeat(time_width as usize), None)
+                });
+            }
+            buffer.extend(line);
+            if let (Some(path), 0) = (&path, index) {
+                buffer.push(Segment::new(path, Some(Style::parse("dim").unwrap())));
+            }
+            buffer.push(Segment::line());
+        }
+
+        self.write_segments(&buffer)
+    }
+
+    /// Draw a line with an optional centered title.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - Text to render in the rule, or an empty string for no title.
+    ///
+    /// # Errors
+    ///
+    /// Any error from writing to the console's file.
+    pub fn rule(&mut self, title: &str) -> io::Result<()> { self.print(&Rule::new(title)) }
+
+    /// Generate text from everything recorded by the console.
+    ///
+    /// The console must have been created with [`Console::with_record`].
+    ///
+    /// # Arguments
+    ///
+    /// * `styles` - Keep styles and control codes as ANSI escape sequences,
+    ///   rather than returning plain text.
+    /// * `clear` - Clear the record buffer after exporting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::console::Console;
+    /// use wealthy::text::Text;
+    ///
+    /// let mut console = Console::new().with_file(Vec::new()).with_record(true);
+    /// console
+    ///     .print(&Text::from_markup("[bold]Hello[/]").unwrap())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(console.export_text(false, false), "Hello\n");
+    /// assert_eq!(console.export_text(true, true), "\x1b[1mHello\x1b[0m\n");
+    /// ```
+    pub fn export_text(&mut self, styles: bool, clear: bool) -> String {
+        let text = if styles {
+            render_ansi(&self.record_buffer, Some(ColorSystem::TrueColor), true)
+        } else {
+            render_ansi(&self.record_buffer, None, false)
+        };
+
+        if clear {
+            self.record_buffer.clear();
+        }
+
+        text
+    }
+
+    /// Generate text from everything recorded by the console and write it to
+    /// a file, clearing the record buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to write the text file to.
+    /// * `styles` - Keep styles and control codes as ANSI escape sequences,
+    ///   rather than writing plain text.
+    ///
+    /// # Errors
+    ///
+    /// Any error from writing the file.
+    pub fn save_text(&mut self, path: impl AsRef<Path>, styles: bool) -> io::Result<()> {
+        fs::write(path, self.export_text(styles, true))
+    }
+
+    /// Generate HTML from everything recorded by the console.
+    ///
+    /// The console must have been created with [`Console::with_record`].
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - The theme to resolve colors with, or `None` for the default
+    ///   theme.
+    /// * `inline_styles` - Put styles in a `style` attribute on every span,
+    ///   rather than in a stylesheet. This makes the output larger, but easier
+    ///   to cut and paste.
+    /// * `clear` - Clear the record buffer after exporting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::console::Console;
+    /// use wealthy::text::Text;
+    ///
+    /// let mut console = Console::new().with_file(Vec::new()).with_record(true);
+    /// console
+    ///     .print(&Text::from_markup("[bold]Hello[/]").unwrap())
+    ///     .unwrap();
+    ///
+    /// let html = console.export_html(None, false, true);
+    ///
+    /// assert!(html.contains(".r1 {font-weight: bold}"));
+    /// assert!(html.contains("<span class=\"r1\">Hello</span>"));
+    /// ```
+    pub fn export_html(
+        &mut self,
+        theme: Option<&TerminalTheme>,
+        inline_styles: bool,
+        clear: bool,
+    ) -> String {
+        let theme = theme.unwrap_or(&DEFAULT_TERMINAL_THEME);
+        let segments = Segment::filter_control(&Segment::simplify(&self.record_buffer), false);
+        let mut code = String::new();
+        // Style rules of the stylesheet, in the order they were first used.
+        let mut rules: Vec<String> = vec![];
+
+        for segment in &segments {
+            let text = escape_html(&segment.text);
+            let Some(style) = &segment.style else {
+                code.push_str(&text);
+                continue;
+            };
+
+            let rule = style.get_html_style(Some(theme));
+            if inline_styles {
+                let text = match &style.link {
+                    Some(link) => format!("<a href=\"{}\">{text}</a>", escape_html(link)),
+                    None => text,
+                };
+
+                if rule.is_empty() {
+                    code.push_str(&text);
+                } else {
+                    code.push_str(&format!("<span style=\"{rule}\">{text}</span>"));
+                }
+            } else {
+                let style_number = rules
+                    .iter()
+                    .position(|existing| *existing == rule)
+                    .unwrap_or_else(|| {
+                        rules.push(rule);
+                        rules.len() - 1
+                    })
+                    + 1;
+
+                code.push_str(&match &style.link {
+                    Some(link) => format!(
+                        "<a class=\"r{style_number}\" href=\"{}\">{text}</a>",
+                        escape_html(link)
+                    ),
+                    None => format!("<span class=\"r{style_number}\">{text}</span>"),
+                });
+            }
+        }
+
+        let stylesheet = rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| !rule.is_empty())
+            .map(|(index, rule)| format!(".r{} {{{rule}}}", index + 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if clear {
+            self.record_buffer.clear();
+        }
+
+        render_format(
+            CONSOLE_HTML_FORMAT,
+            &[
+                ("code", &code),
+                ("stylesheet", &stylesheet),
+                ("foreground", &theme.foreground_color.hex()),
+                ("background", &theme.background_color.hex()),
+            ],
+        )
+    }
+
+    /// Generate HTML from everything recorded by the console and write it to
+    /// a file, clearing the record buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to write the HTML file to.
+    /// * `theme` - The theme to resolve colors with, or `None` for the default
+    ///   theme.
+    /// * `inline_styles` - Put styles in a `style` attribute on every span,
+    ///   rather than in a stylesheet.
+    ///
+    /// # Errors
+    ///
+    /// Any error from writing the file.
+    pub fn save_html(
+        &mut self,
+        path: impl AsRef<Path>,
+        theme: Option<&TerminalTheme>,
+        inline_styles: bool,
+    ) -> io::Result<()> {
+        fs::write(path, self.export_html(theme, inline_styles, true))
+    }
+
+    /// Generate an SVG, styled like a terminal window, from everything recorded
+    /// by the console.
+    ///
+    /// The console must have been created with [`Console::with_record`].
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The title of the terminal window, or an empty string for no
+    ///   title.
+    /// * `theme` - The theme to resolve colors with, or `None` for the SVG
+    ///   export theme.
+    /// * `clear` - Clear the record buffer after exporting.
+    /// * `unique_id` - Prefix for the CSS classes of the SVG, or `None` to
+    ///   derive one from the recorded content and the title. Set this to embed
+    ///   several SVGs in a page without their styles conflicting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::console::Console;
+    ///
+    /// let mut console = Console::new().with_file(Vec::new()).with_record(true);
+    /// console.print("Hello, World!").unwrap();
+    ///
+    /// let svg = console.export_svg("Example", None, true, Some("example"));
+    ///
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(">Hello,&#160;World!</text>"));
+    /// ```
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::too_many_lines)]
+    pub fn export_svg(
+        &mut self,
+        title: &str,
+        theme: Option<&TerminalTheme>,
+        clear: bool,
+        unique_id: Option<&str>,
+    ) -> String {
+        const CHAR_HEIGHT: f64 = 20.0;
+        const CHAR_WIDTH: f64 = CHAR_HEIGHT * 0.61;
+        const LINE_HEIGHT: f64 = CHAR_HEIGHT * 1.22;
+        const MARGIN: f64 = 1.0;
+        const PADDING_TOP: f64 = 40.0;
+        const PADDING: f64 = 8.0;
+
+        let theme = theme.unwrap_or(&SVG_EXPORT_THEME);
+        let width = self.width();
+        let segments = Segment::filter_control(&self.record_buffer, false);
+        if clear {
+            self.record_buffer.clear();
+        }
+
+        let unique_id = unique_id.map_or_else(
+            || {
+                // Hash the text and style definitions rather than `Debug` output, which
+                // changes with the fields of the types.
+                let mut content = String::new();
+                for segment in &segments {
+                    content.push_str(&segment.text);
+                    content.push('\0');
+                    if let Some(style) = &segment.style {
+                        content.push_str(&style.to_string());
+                    }
+                    content.push('\0');
+                }
+                content.push_str(title);
+
+                format!("{_SVG_CLASSES_PREFIX}-{}", adler32(content.as_bytes()))
+            },
+            String::from,
+        );
+        let escape_text = |text: &str| escape_html(text).replace(' ', "&#160;");
+
+        // CSS rules of the text classes, in the order they were first used.
+        let mut classes: Vec<String> = vec![];
+        let mut backgrounds = String::new();
+        let mut matrix = String::new();
+        let lines = Segment::split_and_crop_lines(&segments, width, None, true, false);
+
+        for (y, line) in lines.iter().enumerate() {
+            let y = y as f64;
+            let mut x = 0;
+
+            for segment in line {
+                let style = segment.style.clone().unwrap_or_default();
+                let mut color = style.theme_color(theme).unwrap_or(theme.foreground_color);
+                let mut bgcolor = match &style.bgcolor {
+                    Some(bgcolor) if !bgcolor.is_default() => {
+                        bgcolor.get_truecolor(Some(theme), false)
+                    },
+                    _ => theme.background_color,
+                };
+                let reverse = style.reverse == Some(true);
+                if reverse {
+                    (color, bgcolor) = (bgcolor, color);
+                }
+
+                let mut rules = vec![];
+                if style.dim == Some(true) {
+                    rules.push(format!("fill: {}", color.blend(&bgcolor, 0.4).hex()));
+                } else {
+                    rules.push(format!("fill: {}", color.hex()));
+                }
+                for (attribute, rule) in [
+                    (style.bold, "font-weight: bold"),
+                    (style.italic, "font-style: italic;"),
+                    (style.underline, "text-decoration: underline;"),
+                    (style.strike, "text-decoration: line-through;"),
+                ] {
+                    if attribute == Some(true) {
+                        rules.push(rule.into());
+                    }
+                }
+                let rules = rules.join(";");
+                let class_number = classes
+                    .iter()
+                    .position(|existing| *existing == rules)
+                    .unwrap_or_else(|| {
+                        classes.push(rules);
+                        classes.len() - 1
+                    })
+                    + 1;
+
+                let text_length = f64::from(segment.cell_length());
+                let has_background = reverse
+                    || style
+                        .bgcolor
+                        .as_ref()
+                        .is_some_and(|color| !color.is_default());
+                if has_background {
+                    backgrounds.push_str(&make_svg_tag(
+                        "rect",
+                        None,
+                        &[
+                            ("fill", bgcolor.hex()),
+                            ("x", format_svg_number(f64::from(x) * CHAR_WIDTH)),
+                            ("y", format_svg_number(y * LINE_HEIGHT + 1.5)),
+                            ("width", format_svg_number(CHAR_WIDTH * text_length)),
+                            ("height", format_svg_number(LINE_HEIGHT + 0.25)),
+                            ("shape-rendering", "crispEdges".into()),
+                        ],
+                    ));
+                }
+
+                if !segment.text.chars().all(|character| character == ' ') {
+                    matrix.push_str(&make_svg_tag(
+                        "text",
+                        Some(&escape_text(&segment.text)),
+                        &[
+                            ("class", format!("{unique_id}-r{class_number}")),
+                            ("x", format_svg_number(f64::from(x) * CHAR_WIDTH)),
+                            ("y", format_svg_number(y * LINE_HEIGHT + CHAR_HEIGHT)),
+                            ("textLength", format_svg_number(CHAR_WIDTH * text_length)),
+                        ],
+                    ));
+                }
+                x += segment.cell_length();
+            }
+        }
+
+        let styles = classes
+            .iter()
+            .enumerate()
+            .map(|(index, rules)| format!(".{unique_id}-r{} {{ {rules} }}", index + 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let line_count = lines.len().max(1) as f64;
+        let terminal_width = (f64::from(width) * CHAR_WIDTH + PADDING * 2.0).ceil();
+        let terminal_height = line_count * LINE_HEIGHT + PADDING_TOP + PADDING;
+
+        let mut chrome = make_svg_tag(
+            "rect",
+            None,
+            &[
+                ("fill", theme.background_color.hex()),
+                ("stroke", "rgba(255,255,255,0.35)".into()),
+                ("stroke-width", "1".into()),
+                ("x", format_svg_number(MARGIN)),
+                ("y", format_svg_number(MARGIN)),
+                ("width", format_svg_number(terminal_width)),
+                ("height", format_svg_number(terminal_height)),
+                ("rx", "8".into()),
+            ],
+        );
+        if !title.is_empty() {
+            chrome.push_str(&make_svg_tag(
+                "text",
+                Some(&escape_text(title)),
+                &[
+                    ("class", format!("{unique_id}-title")),
+                    ("fill", theme.foreground_color.hex()),
+                    ("text-anchor", "middle".into()),
+                    ("x", format_svg_number((terminal_width / 2.0).floor())),
+                    ("y", format_svg_number(MARGIN + CHAR_HEIGHT + 6.0)),
+                ],
+            ));
+        }
+        chrome.push_str(
+            r##"
+        <g transform="translate(26,22)">
+        <circle cx="0" cy="0" r="7" fill="#ff5f57"/>
+        <circle cx="22" cy="0" r="7" fill="#febc2e"/>
+        <circle cx="44" cy="0" r="7" fill="#28c840"/>
+        </g>
+    "##,
+        );
+
+        render_format(
+            CONSOLE_SVG_FORMAT,
+            &[
+                ("unique_id", &unique_id),
+                ("char_height", &format_svg_number(CHAR_HEIGHT)),
+                ("line_height", &format_svg_number(LINE_HEIGHT)),
+                ("width", &format_svg_number(terminal_width + MARGIN * 2.0)),
+                ("height", &format_svg_number(terminal_height + MARGIN * 2.0)),
+                ("terminal_x", &format_svg_number(MARGIN + PADDING)),
+                ("terminal_y", &format_svg_number(MARGIN + PADDING_TOP)),
+                ("styles", &styles),
+                ("chrome", &chrome),
+                ("backgrounds", &backgrounds),
+                ("matrix", &matrix),
+            ],
+        )
+    }
+
+    /// Generate an SVG from everything recorded by the console and write it to
+    /// a file, clearing the record buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to write the SVG file to.
+    /// * `title` - The title of the terminal window, or an empty string for no
+    ///   title.
+    /// * `theme` - The theme to resolve colors with, or `None` for the SVG
+    ///   export theme.
+    /// * `unique_id` - Prefix for the CSS classes of the SVG, or `None` to
+    ///   derive one from the recorded content and the title.
+    ///
+    /// # Errors
+    ///
+    /// Any error from writing the file.
+    pub fn save_svg(
+        &mut self,
+        path: impl AsRef<Path>,
+        title: &str,
+        theme: Option<&TerminalTheme>,
+        unique_id: Option<&str>,
+    ) -> io::Result<()> {
+        fs::write(path, self.export_svg(title, theme, true, unique_id))
+    }
+
+    /// Write segments to the console's file, rendering styles and control
+    /// codes in to ANSI escape sequences.
+    fn write_segments(&mut self, segments: &[Segment]) -> io::Result<()> {
+        if self.record {
+            self.record_buffer.extend_from_slice(segments);
+        }
+
+        let output = render_ansi(segments, self.color_system(), self.is_terminal);
+
+        self.file.write_all(output.as_bytes())?;
+        self.file.flush()
+    }
+}
+
+/// Detect the color system supported by a terminal from its environment
+/// variables.
+///
+/// In order of precedence:
+///
+/// * A non-empty `NO_COLOR` disables color.
+/// * `FORCE_COLOR` set to anything but `0` or `false`, or `CLICOLOR_FORCE` set
+///   to anything but `0`, enables color even if the output isn't a terminal.
+///   `FORCE_COLOR=0` disables color.
+/// * Otherwise there is no color if the output isn't a terminal, or `CLICOLOR`
+///   is `0`.
+/// * `TERM` set to `dumb` or `unknown` disables color.
+/// * `COLORTERM` set to `truecolor` or `24bit` selects
+///   [`ColorSystem::TrueColor`].
+/// * `TERM` ending in `-direct`, `-truecolor` or `-kitty` selects
+///   [`ColorSystem::TrueColor`], and ending in `-256color` selects
+///   [`ColorSystem::EightBit`].
+/// * Anything else gets [`ColorSystem::Standard`].
+///
+/// # Arguments
+///
+/// * `environ` - The environment variables.
+/// * `is_terminal` - If the output is a terminal.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use wealthy::color::ColorSystem;
+/// use wealthy::console::detect_color_system;
+///
+/// let environ = HashMap::from([("TERM".into(), "xterm-256color".into())]);
+///
+/// assert_eq!(
+///     detect_color_system(&environ, true),
+///     Some(ColorSystem::EightBit)
+/// );
+/// assert_eq!(detect_color_system(&environ, false), None);
+/// ```
+pub fn detect_color_system<S: BuildHasher>(
+    environ: &HashMap<String, String, S>,
+    is_terminal: bool,
+) -> Option<ColorSystem> {
+    let get = |key: &str| {
+        environ
+            .get(key)
+            .map(|value| value.trim().to_lowercase())
+            .filter(|value| !value.is_empty())
+    };
+
+    if get("NO_COLOR").is_some() {
+        return None;
+    }
+    let force_color = get("FORCE_COLOR");
+    if matches!(force_color.as_deref(), Some("0" | "false")) {
+        return None;
+    }
+    let forced = force_color.is_some() || get("CLICOLOR_FORCE").is_some_and(|value| value != "0");
+    if !forced && (!is_terminal || get("CLICOLOR").as_deref() == Some("0")) {
+        return None;
+    }
+
+    let term = get("TERM").unwrap_or_default();
+    if matches!(term.as_str(), "dumb" | "unknown") {
+        return None;
+    }
+    if matches!(get("COLORTERM").as_deref(), Some("truecolor" | "24bit")) {
+        return Some(ColorSystem::TrueColor);
+    }
+
+    match term.rsplit_once('-').map(|(_, suffix)| suffix) {
+        Some("direct" | "truecolor" | "kitty") => Some(ColorSystem::TrueColor),
+        Some("256color") => Some(ColorSystem::EightBit),
+        _ => Some(ColorSystem::Standard),
+    }
+}
+
+/// Render segments in to text with ANSI escape sequences.
+///
+/// # Arguments
+///
+/// * `segments` - The segments to render.
+/// * `color_system` - The color system to render styles to, or `None` to leave
+///   the text unstyled.
+/// * `control` - Render control codes, rather than dropping them.
+fn render_ansi(segments: &[Segment], color_system: Option<ColorSystem>, control: bool) -> String {
+    let mut output = String::new();
+
+    for segment in segments {
+        if let Some(codes) = &segment.control {
+            if control {
+                for code in codes {
+                    output.push_str(&code.to_string());
+                }
+            }
+        } else if let Some(style) = &segment.style {
+            output.push_str(&style.render(&segment.text, color_system));
+        } else {
+            output.push_str(&segment.text);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::segment::ControlCode;
+    use crate::text::Text;
+
+    /// Run `f` with a 20 cell wide console and return what was written.
+    fn capture(f: impl FnOnce(&mut Console<'_>)) -> String {
+        let mut output = vec![];
+        let mut console = Console::new()
+            .with_file(&mut output)
+            .with_width(20)
+            .with_color_system(None)
+            .with_log_columns(false, false);
+
+        f(&mut console);
+        drop(console);
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_print() {
+        assert_eq!(capture(|console| console.print("foo").unwrap()), "foo\n");
+        assert_eq!(
+            capture(|console| console.print("foo\nbar").unwrap()),
+            "foo\nbar\n"
+        );
+        assert_eq!(
+            capture(|console| console.print(&"x".repeat(30)).unwrap()),
+            format!("{}\n", "x".repeat(20))
+        );
+    }
+
+    #[test]
+    fn test_print_styled() {
+        let mut output = vec![];
+        let mut console = Console::new()
+            .with_file(&mut output)
+            .with_color_system(Some(ColorSystem::TrueColor));
+
+        console
+            .print_styled("foo", Some(&Style::parse("bold").unwrap()))
+            .unwrap();
+        drop(console);
+
+        assert_eq!(String::from_utf8(output).unwrap(), "\x1b[1mfoo\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_log() {
+        assert_eq!(capture(|console| console.log("foo").unwrap()), "foo\n");
+
+        let mut output = vec![];
+        let mut console = Console::new()
+            .with_file(&mut output)
+            .with_width(40)
+            .with_color_system(None);
+        let line = line!() + 1;
+        console.log("foo").unwrap();
+        drop(console);
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with('['));
+        assert!(output.contains("] foo"));
+        assert!(output.ends_with(&format!(" console.rs:{line}\n")));
+        assert_eq!(cell_len(output.trim_end_matches('\n')), 40);
+    }
+
+    #[test]
+    fn test_rule() {
+        assert_eq!(
+            capture(|console| console.rule("").unwrap()),
+            format!("{}\n", "─".repeat(20))
+        );
+        assert_eq!(
+            capture(|console| console.rule("foo").unwrap()),
+            "─────── foo ────────\n"
+        );
+    }
+
+    #[rstest]
+    #[case(None, None, &[("COLUMNS", "100"), ("LINES", "50")], 100, 50)]
+    #[case(Some(20), Some(10), &[("COLUMNS", "100"), ("LINES", "50")], 20, 10)]
+    #[case(Some(20), None, &[("LINES", "50")], 20, 50)]
+    #[case(None, Some(10), &[("COLUMNS", "100")], 100, 10)]
+    fn test_size(
+        #[case] width: Option<u32>,
+        #[case] height: Option<u32>,
+        #[case] environ: &[(&str, &str)],
+        #[case] expected_width: u32,
+        #[case] expected_height: u32,
+    ) {
+        let mut console = Console::new().with_environ(
+            environ
+                .iter()
+                .map(|(key, value)| ((*key).into(), (*value).into()))
+                .collect(),
+        );
+        if let Some(width) = width {
+            console = console.with_width(width);
+        }
+        if let Some(height) = height {
+            console = console.with_height(height);
+        }
+
+        assert_eq!(
+            console.size(),
+            ConsoleDimensions {
+                width: expected_width,
+                height: expected_height
+            }
+        );
+    }
+
+    #[test]
+    fn test_color_system() {
+        let console = Console::new()
+            .with_file(io::sink())
+            .with_environ(HashMap::new());
+
+        assert_eq!(console.color_system(), None);
+        assert_eq!(
+            console.with_force_terminal(true).color_system(),
+            Some(ColorSystem::Standard)
+        );
+
+        let environ = HashMap::from([("COLORTERM".into(), "truecolor".into())]);
+        let console = Console::new()
+            .with_file(io::sink())
+            .with_force_terminal(true)
+            .with_environ(environ);
+
+        assert_eq!(console.color_system(), Some(ColorSystem::TrueColor));
+        assert_eq!(console.with_color_system(None).color_system(), None);
+    }
+
+    #[test]
+    fn test_options() {
+        let options = Console::new().with_width(20).with_height(10).options();
+
+        assert_eq!(options.max_width, 20);
+        assert_eq!(options.update_width(5).min_width, 5);
+        assert_eq!(options.update_height(5).height, Some(5));
+        assert_eq!(options.update_height(5).reset_height().height, None);
+        assert_eq!(
+            options.update_dimensions(1, 2),
+            ConsoleOptions {
+                min_width: 1,
+                max_width: 1,
+                max_height: 2,
+                height: Some(2),
+                ..options
+            }
+        );
+    }
+
+    #[test]
+    fn test_render_lines() {
+        let console = Console::new().with_width(5);
+
+        assert_eq!(
+            console.render_lines("foo\nbar", None, None, true, false),
+            vec![
+                vec![Segment::new("foo", None), Segment::new("  ", None)],
+                vec![Segment::new("bar", None), Segment::new("  ", None)],
+            ]
+        );
+    }
+
+    /// A 20 cell wide recording console that discards its output.
+    fn recording_console() -> Console<'static> {
+        Console::new()
+            .with_file(io::sink())
+            .with_width(20)
+            .with_record(true)
+    }
+
+    #[test]
+    fn test_export_html() {
+        let mut console = recording_console();
+        console
+            .print(&Text::from_markup("[b red on blue]foo[/] [i]<bar>[/]").unwrap())
+            .unwrap();
+        let html = console.export_html(None, false, true);
+
+        assert_eq!(
+            html,
+            render_format(
+                CONSOLE_HTML_FORMAT,
+                &[
+                    (
+                        "stylesheet",
+                        ".r1 {color: #800000; text-decoration-color: #800000; background-color: \
+                         #000080; font-weight: bold}\n.r2 {font-style: italic}"
+                    ),
+                    (
+                        "code",
+                        "<span class=\"r1\">foo</span> <span class=\"r2\">&lt;bar&gt;</span>\n"
+                    ),
+                    ("foreground", "#000000"),
+                    ("background", "#ffffff"),
+                ]
+            )
+        );
+        assert!(html.contains("color: #000000;\n    background-color: #ffffff;"));
+    }
+
+    #[test]
+    fn test_export_html_inline_styles() {
+        let mut console = recording_console();
+        let link = Style {
+            link: Some("https://example.org".into()),
+            ..Style::parse("italic").unwrap()
+        };
+        console.print_styled("foo", Some(&link)).unwrap();
+        let html = console.export_html(None, true, true);
+
+        assert!(html.contains(
+            "<span style=\"font-style: italic\"><a href=\"https://example.org\">foo</a></span>\n"
+        ));
+        assert!(html.contains("<style>\n\nbody"));
+    }
+
+    #[rstest]
+    #[case(true, "<a href=\"https://example.org/?q=&quot;&gt;&lt;b&gt;\">foo</a>")]
+    #[case(
+        false,
+        "<a class=\"r1\" href=\"https://example.org/?q=&quot;&gt;&lt;b&gt;\">foo</a>"
+    )]
+    fn test_export_html_escape_link(#[case] inline_styles: bool, #[case] anchor: &str) {
+        let mut console = recording_console();
+        let link = Style {
+            link: Some("https://example.org/?q=\"><b>".into()),
+            ..Style::parse("italic").unwrap()
+        };
+        console.print_styled("foo", Some(&link)).unwrap();
+        let html = console.export_html(None, inline_styles, true);
+
+        assert!(html.contains(anchor));
+        assert!(!html.contains("<b>"));
+    }
+
+    #[test]
+    fn test_export_html_clear() {
+        let mut console = recording_console();
+        console.print("foo").unwrap();
+
+        assert!(console.export_html(None, false, false).contains("foo"));
+        assert!(console.export_html(None, false, true).contains("foo"));
+        assert!(!console.export_html(None, false, true).contains("foo"));
+    }
+
+    #[test]
+    fn test_export_html_not_recording() {
+        let mut console = recording_console().with_record(false);
+        console.print("foo").unwrap();
+
+        assert!(!console.export_html(None, false, true).contains("foo"));
+    }
+
+    /// Print some markup to a 10 cell wide recording console and export it
+    /// as SVG.
+    fn export_svg(markup: &str, title: &str, unique_id: Option<&str>) -> String {
+        let mut console = recording_console().with_width(10);
+        console.print(&Text::from_markup(markup).unwrap()).unwrap();
+
+        console.export_svg(title, None, true, unique_id)
+    }
+
+    #[test]
+    fn test_export_svg() {
+        let svg = export_svg(
+            "[bold red on blue]ab[/] 你好\n[reverse]x[/]",
+            "Demo",
+            Some("t"),
+        );
+
+        assert!(svg.starts_with("<svg class=\"rich-terminal\" viewBox=\"0 0 140 98.8\""));
+        assert!(svg.contains(".t-r1 { fill: #cc555a;font-weight: bold }\n.t-r2 { fill: #c5c8c6 }"));
+        assert!(svg.contains(
+            "<text class=\"t-title\" fill=\"#c5c8c6\" text-anchor=\"middle\" x=\"69\" \
+             y=\"27\">Demo</text>"
+        ));
+        assert!(svg.contains(
+            "<rect fill=\"#608ab1\" x=\"0\" y=\"1.5\" width=\"24.4\" height=\"24.65\" \
+             shape-rendering=\"crispEdges\"/><rect fill=\"#c5c8c6\" x=\"0\" y=\"25.9\" \
+             width=\"12.2\" height=\"24.65\" shape-rendering=\"crispEdges\"/>"
+        ));
+        assert!(svg.contains(
+            "<text class=\"t-r1\" x=\"0\" y=\"20\" textLength=\"24.4\">ab</text><text \
+             class=\"t-r2\" x=\"24.4\" y=\"20\" textLength=\"61\">&#160;你好</text><text \
+             class=\"t-r3\" x=\"0\" y=\"44.4\" textLength=\"12.2\">x</text>"
+        ));
+    }
+
+    #[test]
+    fn test_export_svg_unique_id() {
+        let unique_id = |svg: &str| {
+            let start = svg.find(".rich-svg-").unwrap() + 1;
+            let end = svg.find("-matrix").unwrap();
+
+            svg[start..end].to_owned()
+        };
+        let svg = export_svg("foo", "Title", None);
+
+        assert_eq!(
+            unique_id(&svg),
+            unique_id(&export_svg("foo", "Title", None))
+        );
+        assert_ne!(
+            unique_id(&svg),
+            unique_id(&export_svg("bar", "Title", None))
+        );
+        assert_ne!(
+            unique_id(&svg),
+            unique_id(&export_svg("foo", "Other", None))
+        );
+        assert_ne!(
+            unique_id(&svg),
+            unique_id(&export_svg("[bold]foo[/]", "Title", None))
+        );
+        assert_eq!(unique_id(&svg), "rich-svg-358941521");
+    }
+
+    #[test]
+    fn test_export_svg_no_title() {
+        assert!(!export_svg("foo", "", Some("t")).contains("t-title\" fill"));
+    }
+
+    #[test]
+    fn test_export_text() {
+        let mut console = recording_console().with_force_terminal(true);
+        console
+            .print(&Text::from_markup("[bold]foo[/] bar").unwrap())
+            .unwrap();
+        console
+            .write_segments(&[Segment::new_control(vec![ControlCode::Bell])])
+            .unwrap();
+
+        assert_eq!(console.export_text(false, false), "foo bar\n");
+        assert_eq!(
+            console.export_text(true, true),
+            "\x1b[1mfoo\x1b[0m bar\n\x07"
+        );
+        assert_eq!(console.export_text(false, true), "");
+    }
+
+    #[test]
+    fn test_save_text() {
+        let path =
+            std::env::temp_dir().join(format!("wealthy-save-text-{}.txt", std::process::id()));
+        let mut console = recording_console();
+        console.print("foo").unwrap();
+        console.save_text(&path, false).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "foo\n");
+        assert_eq!(console.export_text(false, true), "");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[rstest]
+    #[case(&[], true, Some(ColorSystem::Standard))]
+    #[case(&[], false, None)]
+    #[case(&[("TERM", "xterm-256color")], true, Some(ColorSystem::EightBit))]
+    #[case(&[("TERM", "xterm-256color")], false, None)]
+    #[case(&[("TERM", "xterm-kitty")], true, Some(ColorSystem::TrueColor))]
+    #[case(&[("TERM", "xterm-direct")], true, Some(ColorSystem::TrueColor))]
+    #[case(&[("TERM", "xterm")], true, Some(ColorSystem::Standard))]
+    #[case(&[("TERM", "dumb")], true, None)]
+    #[case(&[("TERM", "unknown"), ("FORCE_COLOR", "1")], true, None)]
+    #[case(&[("COLORTERM", "truecolor")], true, Some(ColorSystem::TrueColor))]
+    #[case(&[("COLORTERM", "24bit"), ("TERM", "xterm-256color")], true, Some(ColorSystem::TrueColor))]
+    #[case(&[("COLORTERM", "yes"), ("TERM", "xterm-256color")], true, Some(ColorSystem::EightBit))]
+    #[case(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")], true, None)]
+    #[case(&[("NO_COLOR", "1"), ("FORCE_COLOR", "1")], false, None)]
+    #[case(&[("NO_COLOR", ""), ("TERM", "xterm")], true, Some(ColorSystem::Standard))]
+    #[case(&[("FORCE_COLOR", "1")], false, Some(ColorSystem::Standard))]
+    #[case(&[("FORCE_COLOR", "true"), ("COLORTERM", "truecolor")], false, Some(ColorSystem::TrueColor))]
+    #[case(&[("FORCE_COLOR", "0")], true, None)]
+    #[case(&[("FORCE_COLOR", "")], false, None)]
+    #[case(&[("CLICOLOR_FORCE", "1")], false, Some(ColorSystem::Standard))]
+    #[case(&[("CLICOLOR_FORCE", "0")], false, None)]
+    #[case(&[("CLICOLOR", "0")], true, None)]
+    #[case(&[("CLICOLOR", "0"), ("CLICOLOR_FORCE", "1")], true, Some(ColorSystem::Standard))]
+    #[case(&[("CLICOLOR", "1")], false, None)]
+    fn test_detect_color_system(
+        #[case] environ: &[(&str, &str)],
+        #[case] is_terminal: bool,
+        #[case] result: Option<ColorSystem>,
+    ) {
+        let environ: HashMap<String, String> = environ
+            .iter()
+            .map(|(key, value)| ((*key).into(), (*value).into()))
+            .collect();
+
+        assert_eq!(detect_color_system(&environ, is_terminal), result);
+    }
+}
@@ -0,0 +1,432 @@
+//! Divide space between edges (such as the rows or columns of a dashboard)
+//! with constraints.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// How much space an edge asks for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Size {
+    /// An exact number of cells.
+    Length(u32),
+    /// A percentage (from 0 to 100) of the total space.
+    Percentage(u32),
+    /// A share of the space left over by [`Size::Length`] and
+    /// [`Size::Percentage`] edges, weighted against the other ratios.
+    Ratio(u32),
+    /// An equal share of whatever space is left once every other edge has its
+    /// size.
+    Fill,
+}
+
+/// A constraint on the size of an edge.
+///
+/// The size asked for is clamped between the minimum and maximum sizes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Constraint {
+    /// The space asked for.
+    pub size: Size,
+    /// The smallest size the edge may have.
+    pub minimum_size: u32,
+    /// The largest size the edge may have, if any.
+    pub maximum_size: Option<u32>,
+}
+
+impl Constraint {
+    const fn new(size: Size) -> Self {
+        Self {
+            size,
+            minimum_size: 0,
+            maximum_size: None,
+        }
+    }
+
+    /// Get a [`Constraint`] for an exact number of cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The number of cells.
+    pub const fn length(length: u32) -> Self { Self::new(Size::Length(length)) }
+
+    /// Get a [`Constraint`] for a percentage of the total space.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentage` - The percentage, from 0 to 100.
+    pub const fn percentage(percentage: u32) -> Self { Self::new(Size::Percentage(percentage)) }
+
+    /// Get a [`Constraint`] for a share of the space left over by lengths and
+    /// percentages.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - The weight of the share.
+    pub const fn ratio(ratio: u32) -> Self { Self::new(Size::Ratio(ratio)) }
+
+    /// Get a [`Constraint`] that fills the space left once every other edge has
+    /// its size.
+    pub const fn fill() -> Self { Self::new(Size::Fill) }
+
+    /// Set the smallest size the edge may have.
+    ///
+    /// # Arguments
+    ///
+    /// * `minimum_size` - The minimum size.
+    #[must_use]
+    pub const fn with_minimum_size(mut self, minimum_size: u32) -> Self {
+        self.minimum_size = minimum_size;
+        self
+    }
+
+    /// Set the largest size the edge may have.
+    ///
+    /// # Arguments
+    ///
+    /// * `maximum_size` - The maximum size.
+    #[must_use]
+    pub const fn with_maximum_size(mut self, maximum_size: u32) -> Self {
+        self.maximum_size = Some(maximum_size);
+        self
+    }
+
+    /// Set the smallest and largest sizes the edge may have.
+    ///
+    /// # Arguments
+    ///
+    /// * `minimum_size` - The minimum size.
+    /// * `maximum_size` - The maximum size.
+    #[must_use]
+    pub const fn with_range(self, minimum_size: u32, maximum_size: u32) -> Self {
+        self.with_minimum_size(minimum_size)
+            .with_maximum_size(maximum_size)
+    }
+
+    /// Clamp a size between the minimum and maximum sizes.
+    fn clamp(&self, size: u32) -> u32 {
+        let size = size.max(self.minimum_size);
+        self.maximum_size.map_or(size, |maximum| size.min(maximum))
+    }
+
+    /// Check the constraint makes sense on its own.
+    const fn validate(&self) -> Result<(), ConstraintError> {
+        if let Size::Percentage(percentage) = self.size {
+            if percentage > 100 {
+                return Err(ConstraintError::Percentage(percentage));
+            }
+        }
+        if let Some(maximum_size) = self.maximum_size {
+            if self.minimum_size > maximum_size {
+                return Err(ConstraintError::Range {
+                    minimum_size: self.minimum_size,
+                    maximum_size,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error from [`solve`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ConstraintError {
+    /// A percentage is over 100, with the percentage given.
+    Percentage(u32),
+    /// A minimum size is larger than its maximum size.
+    Range {
+        /// The minimum size.
+        minimum_size: u32,
+        /// The maximum size.
+        maximum_size: u32,
+    },
+    /// The constraints need more space than there is.
+    Unsatisfiable {
+        /// The space available.
+        total: u32,
+        /// The space the constraints need.
+        required: u64,
+    },
+}
+
+impl Display for ConstraintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Percentage(percentage) => {
+                write!(f, "percentage of {percentage} is over 100")
+            },
+            Self::Range {
+                minimum_size,
+                maximum_size,
+            } => write!(
+                f,
+                "minimum size of {minimum_size} is larger than the maximum size of {maximum_size}"
+            ),
+            Self::Unsatisfiable { total, required } => write!(
+                f,
+                "constraints need {required} cells but there are only {total}"
+            ),
+        }
+    }
+}
+
+impl Error for ConstraintError {}
+
+/// Divide total space between edges with constraints.
+///
+/// The space is handed out in order of priority:
+///
+/// 1. [`Size::Length`] and [`Size::Percentage`] edges get their sizes. Rounding
+///    errors in percentages are carried on to the next percentage, so 50% and
+///    50% of 11 are 5 and 6.
+/// 2. [`Size::Ratio`] edges share the space left, as they would in Rich's
+///    `ratio_resolve`. An edge whose share would fall outside its range is
+///    given its minimum or maximum size, and the rest are shared out again.
+/// 3. [`Size::Fill`] edges share whatever is left, in the same way. Space is
+///    kept back for their minimum sizes during the previous step.
+///
+/// The sizes add up to `total`, unless every edge reaches its maximum (or has
+/// a fixed size) first, in which case there is space left over. Given only
+/// lengths, and ratios with a minimum size, this gives the same sizes as
+/// `ratio_resolve` whenever those fit in to `total`.
+///
+/// # Arguments
+///
+/// * `total` - The total number of cells.
+/// * `constraints` - The constraints for each edge.
+///
+/// # Returns
+///
+/// A vector of number of cells for each edge.
+///
+/// # Errors
+///
+/// [`ConstraintError`] if a constraint is invalid, or if the constraints need
+/// more than `total` cells.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::constraint::{solve, Constraint};
+///
+/// let sizes = solve(
+///     100,
+///     &[
+///         Constraint::length(20),
+///         Constraint::percentage(30),
+///         Constraint::ratio(1).with_maximum_size(10),
+///         Constraint::fill(),
+///     ],
+/// );
+///
+/// assert_eq!(sizes, Ok(vec![20, 30, 10, 40]));
+/// assert!(solve(10, &[Constraint::length(6), Constraint::length(6)]).is_err());
+/// ```
+#[allow(clippy::cast_possible_truncation)]
+pub fn solve(total: u32, constraints: &[Constraint]) -> Result<Vec<u32>, ConstraintError> {
+    for constraint in constraints {
+        constraint.validate()?;
+    }
+
+    let mut sizes: Vec<Option<u32>> = vec![None; constraints.len()];
+
+    let mut percentage_total = 0;
+    for (size, constraint) in sizes.iter_mut().zip(constraints) {
+        match constraint.size {
+            Size::Length(length) => *size = Some(constraint.clamp(length)),
+            Size::Percentage(percentage) => {
+                // Carry the rounding error forward, so the percentages add up.
+                let previous = u64::from(total) * percentage_total / 100;
+                percentage_total += u64::from(percentage);
+                let current = u64::from(total) * percentage_total / 100;
+                *size = Some(constraint.clamp((current - previous) as u32));
+            },
+            Size::Ratio(_) | Size::Fill => {},
+        }
+    }
+
+    let fill_minimums = constraints
+        .iter()
+        .filter(|constraint| constraint.size == Size::Fill)
+        .map(|constraint| u64::from(constraint.minimum_size))
+        .sum();
+    share(total, constraints, &mut sizes, fill_minimums, |size| {
+        if let Size::Ratio(ratio) = size {
+            Some(ratio)
+        } else {
+            None
+        }
+    });
+    share(total, constraints, &mut sizes, 0, |size| {
+        (size == Size::Fill).then_some(1)
+    });
+
+    let sizes: Vec<u32> = sizes.into_iter().flatten().collect();
+    let required = sizes.iter().copied().map(u64::from).sum();
+    if required > u64::from(total) {
+        return Err(ConstraintError::Unsatisfiable { total, required });
+    }
+
+    Ok(sizes)
+}
+
+/// Share the space left between the flexible edges, picked out by `ratio`.
+///
+/// # Arguments
+///
+/// * `total` - The total number of cells.
+/// * `constraints` - The constraints for each edge.
+/// * `sizes` - The sizes of the edges so far.
+/// * `reserved` - Space to keep back for later edges.
+/// * `ratio` - Get the ratio of a flexible edge, or `None` if the edge is
+///   resolved by another call.
+#[allow(clippy::cast_possible_truncation)]
+fn share(
+    total: u32,
+    constraints: &[Constraint],
+    sizes: &mut [Option<u32>],
+    reserved: u64,
+    ratio: impl Fn(Size) -> Option<u32>,
+) {
+    loop {
+        let flexible: Vec<(usize, u128, &Constraint)> = sizes
+            .iter()
+            .zip(constraints)
+            .enumerate()
+            .filter(|(_, (size, _))| size.is_none())
+            .filter_map(|(index, (_, constraint))| {
+                ratio(constraint.size).map(|ratio| (index, u128::from(ratio), constraint))
+            })
+            .collect();
+        if flexible.is_empty() {
+            return;
+        }
+
+        let used: u64 = sizes.iter().flatten().copied().map(u64::from).sum::<u64>() + reserved;
+        let remaining = u64::from(total).saturating_sub(used);
+        if remaining == 0 {
+            // No room for flexible edges.
+            for (index, _, constraint) in flexible {
+                sizes[index] = Some(constraint.minimum_size);
+            }
+            return;
+        }
+
+        // Each edge's share is `remaining * ratio / total_ratio`. The products
+        // can be larger than a `u64`, so they're done in `u128`.
+        let remaining = u128::from(remaining);
+        let total_ratio: u128 = flexible.iter().map(|&(_, ratio, _)| ratio.max(1)).sum();
+
+        // If any edge falls outside of its range, fix its size and share out the
+        // rest again.
+        let outside = flexible.iter().find_map(|&(index, ratio, constraint)| {
+            let share = remaining * ratio;
+            if share <= u128::from(constraint.minimum_size) * total_ratio {
+                Some((index, constraint.minimum_size))
+            } else {
+                constraint
+                    .maximum_size
+                    .filter(|&maximum| share >= u128::from(maximum) * total_ratio)
+                    .map(|maximum| (index, maximum))
+            }
+        });
+        if let Some((index, size)) = outside {
+            sizes[index] = Some(size);
+            continue;
+        }
+
+        // Carry the rounding error forward, so the sizes add up to the remaining
+        // space.
+        let mut ratio_total = 0;
+        for (index, ratio, _) in flexible {
+            let previous = remaining * ratio_total / total_ratio;
+            ratio_total += ratio;
+            sizes[index] = Some((remaining * ratio_total / total_ratio - previous) as u32);
+        }
+        return;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::ratio::{ratio_resolve, Edge};
+
+    #[rstest]
+    #[case(100, &[], &[])]
+    #[case(100, &[Constraint::length(30), Constraint::length(20)], &[30, 20])]
+    #[case(100, &[Constraint::length(30).with_range(40, 50)], &[40])]
+    #[case(11, &[Constraint::percentage(50), Constraint::percentage(50)], &[5, 6])]
+    #[case(10, &[Constraint::percentage(33); 3], &[3, 3, 3])]
+    #[case(100, &[Constraint::percentage(10).with_minimum_size(20), Constraint::ratio(1)], &[20, 80])]
+    #[case(100, &[Constraint::ratio(1), Constraint::ratio(3)], &[25, 75])]
+    #[case(100, &[Constraint::ratio(1).with_maximum_size(10), Constraint::ratio(1)], &[10, 90])]
+    #[case(100, &[Constraint::ratio(1).with_range(10, 20), Constraint::ratio(1).with_maximum_size(30)], &[20, 30])]
+    #[case(100, &[Constraint::ratio(1), Constraint::fill()], &[100, 0])]
+    #[case(100, &[Constraint::ratio(1), Constraint::fill().with_minimum_size(10)], &[90, 10])]
+    #[case(100, &[Constraint::ratio(1).with_maximum_size(40), Constraint::fill(), Constraint::fill()], &[40, 30, 30])]
+    #[case(100, &[Constraint::fill().with_maximum_size(10), Constraint::fill()], &[10, 90])]
+    #[case(100, &[Constraint::fill().with_maximum_size(10)], &[10])]
+    #[case(50, &[Constraint::length(50), Constraint::ratio(1)], &[50, 0])]
+    #[case(50, &[Constraint::length(20), Constraint::percentage(20), Constraint::ratio(1), Constraint::fill()], &[20, 10, 20, 0])]
+    #[case(u32::MAX, &[Constraint::ratio(u32::MAX), Constraint::ratio(u32::MAX)], &[u32::MAX / 2, u32::MAX / 2 + 1])]
+    #[case(u32::MAX, &[Constraint::ratio(u32::MAX).with_minimum_size(u32::MAX - 1), Constraint::ratio(u32::MAX)], &[u32::MAX - 1, 1])]
+    #[case(u32::MAX, &[Constraint::ratio(1).with_maximum_size(u32::MAX), Constraint::fill().with_minimum_size(u32::MAX)], &[0, u32::MAX])]
+    fn test_solve(#[case] total: u32, #[case] constraints: &[Constraint], #[case] result: &[u32]) {
+        assert_eq!(solve(total, constraints).unwrap(), result);
+    }
+
+    #[rstest]
+    #[case(10, &[Constraint::percentage(101)], ConstraintError::Percentage(101))]
+    #[case(10, &[Constraint::ratio(1).with_range(5, 4)], ConstraintError::Range { minimum_size: 5, maximum_size: 4 })]
+    #[case(10, &[Constraint::length(6), Constraint::length(6)], ConstraintError::Unsatisfiable { total: 10, required: 12 })]
+    #[case(10, &[Constraint::length(10), Constraint::ratio(1).with_minimum_size(1)], ConstraintError::Unsatisfiable { total: 10, required: 11 })]
+    #[case(10, &[Constraint::ratio(1).with_minimum_size(6), Constraint::fill().with_minimum_size(6)], ConstraintError::Unsatisfiable { total: 10, required: 12 })]
+    fn test_solve_error(
+        #[case] total: u32,
+        #[case] constraints: &[Constraint],
+        #[case] error: ConstraintError,
+    ) {
+        assert_eq!(solve(total, constraints), Err(error));
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            ConstraintError::Unsatisfiable {
+                total: 10,
+                required: 12
+            }
+            .to_string(),
+            "constraints need 12 cells but there are only 10"
+        );
+    }
+
+    fn constraint(edge: &Edge) -> Constraint {
+        edge.size.map_or_else(
+            || Constraint::ratio(edge.ratio).with_minimum_size(edge.minimum_size),
+            Constraint::length,
+        )
+    }
+
+    #[rstest]
+    #[case(100, vec![Edge { size: Some(100), ..Edge::default() }, Edge::default()])]
+    #[case(100, vec![Edge::default(), Edge::default(), Edge::default()])]
+    #[case(110, vec![Edge::default(), Edge::default(), Edge::default()])]
+    #[case(100, vec![Edge { size: Some(40), ..Edge::default() }, Edge { ratio: 2, ..Edge::default() }, Edge { ratio: 1, minimum_size: 25, ..Edge::default() }])]
+    #[case(50, vec![Edge { size: Some(30), ..Edge::default() }, Edge { ratio: 1, minimum_size: 10, ..Edge::default() }, Edge { size: Some(30), ..Edge::default() }])]
+    #[case(50, vec![Edge { size: Some(30), ..Edge::default() }, Edge { minimum_size: 0, ..Edge::default() }, Edge { size: Some(30), ..Edge::default() }])]
+    #[case(7, vec![Edge { ratio: 3, ..Edge::default() }, Edge { ratio: 0, minimum_size: 0, ..Edge::default() }, Edge { ratio: 5, minimum_size: 3, ..Edge::default() }])]
+    #[case(23, vec![Edge { ratio: 2, minimum_size: 8, ..Edge::default() }, Edge { ratio: 3, ..Edge::default() }, Edge { ratio: 7, ..Edge::default() }])]
+    fn test_ratio_resolve_compatible(#[case] total: u32, #[case] edges: Vec<Edge>) {
+        let expected = ratio_resolve(total, &edges);
+        let constraints: Vec<_> = edges.iter().map(constraint).collect();
+
+        if expected.iter().sum::<u32>() <= total {
+            assert_eq!(solve(total, &constraints).unwrap(), expected);
+        } else {
+            assert!(solve(total, &constraints).is_err());
+        }
+    }
+}
@@ -1,13 +1,18 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
+use lazy_static::lazy_static;
 use regex::{Captures, Regex};
 
 use crate::emoji_codes;
 
+lazy_static! {
+    /// The default pattern for emoji codes, such as `:smiley:` or `:warning-text:`.
+    static ref RE_EMOJI: Regex = Regex::new(r#"(:(\S*?)(?:(?:\-)(emoji|text))?:)"#).unwrap();
+}
+
 /// Replace emoji code in text.
-#[allow(dead_code)]
-fn emoji_replace<'a>(
+pub(crate) fn emoji_replace<'a>(
     text: &'a str,
     default_variant: Option<&str>,
     emoji_regex: Option<Regex>,
@@ -20,8 +25,9 @@ fn emoji_replace<'a>(
         .unwrap_or("");
 
     emoji_regex
-        .unwrap_or_else(|| Regex::new(r#"(:(\S*?)(?:(?:\-)(emoji|text))?:)"#).unwrap())
-        .replace(text, |captures: &Captures| {
+        .as_ref()
+        .unwrap_or(&RE_EMOJI)
+        .replace_all(text, |captures: &Captures| {
             let (emoji_code, emoji_name, variant) = (&captures[1], &captures[2], &captures.get(3));
 
             let emoji = match emoji_codes::EMOJI.get(&*emoji_name.to_lowercase()) {
@@ -47,6 +53,10 @@ mod tests {
             emoji_replace("This is an :atm_sign:", None, None),
             "This is an 🏧"
         );
+        assert_eq!(
+            emoji_replace(":atm_sign: and :atm_sign:", None, None),
+            "🏧 and 🏧"
+        );
     }
 
     #[test]
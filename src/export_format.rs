@@ -65,3 +65,170 @@ pub const CONSOLE_SVG_FORMAT: &str = "<svg class=\"rich-terminal\" viewBox=\"0 0
 ";
 pub const _SVG_FONT_FAMILY: &str = "Rich Fira Code";
 pub const _SVG_CLASSES_PREFIX: &str = "rich-svg";
+
+/// Substitute `{name}` placeholders in one of the export formats, where `{{`
+/// and `}}` are literal braces.
+///
+/// # Arguments
+///
+/// * `format` - The format to fill in.
+/// * `values` - Placeholder names and their values.
+pub(crate) fn render_format(format: &str, values: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(format.len());
+    let mut rest = format;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        output.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            output.push_str(&rest[..1]);
+            rest = &rest[2..];
+        } else if let Some(end) = rest.find('}').filter(|_| rest.starts_with('{')) {
+            match values.iter().find(|(name, _)| *name == &rest[1..end]) {
+                Some((_, value)) => output.push_str(value),
+                None => output.push_str(&rest[..=end]),
+            }
+            rest = &rest[end + 1..];
+        } else {
+            output.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    output.push_str(rest);
+
+    output
+}
+
+/// Escape the characters of `text` that are special in HTML.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+/// Format a number the way the SVG export expects, with at most six
+/// significant digits and no trailing zeros.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn format_svg_number(value: f64) -> String {
+    let integer_digits = if value.abs() >= 1.0 {
+        value.abs().log10().floor() as usize + 1
+    } else {
+        1
+    };
+    let precision = 6_usize.saturating_sub(integer_digits);
+    let formatted = format!("{value:.precision$}");
+
+    if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.').into()
+    } else {
+        formatted
+    }
+}
+
+/// Make an SVG tag.
+///
+/// # Arguments
+///
+/// * `name` - Name of the tag.
+/// * `content` - Content of the tag, or `None` for a self closing tag.
+/// * `attributes` - Attribute names and their values.
+pub(crate) fn make_svg_tag(
+    name: &str,
+    content: Option<&str>,
+    attributes: &[(&str, String)],
+) -> String {
+    let attributes = attributes
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{value}\""))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    match content {
+        Some(content) if !content.is_empty() => format!("<{name} {attributes}>{content}</{name}>"),
+        _ => format!("<{name} {attributes}/>"),
+    }
+}
+
+/// Compute the Adler-32 checksum of some data, used to derive stable ids
+/// from exported content.
+pub(crate) fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let (mut a, mut b) = (1, 0);
+
+    for &byte in data {
+        a = (a + u32::from(byte)) % MOD_ADLER;
+        b = (b + a) % MOD_ADLER;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("{foo}", "bar")]
+    #[case("a {foo} b {foo}", "a bar b bar")]
+    #[case("{{foo}}", "{foo}")]
+    #[case("{{ {foo} }}", "{ bar }")]
+    #[case("{baz}", "{baz}")]
+    #[case("{ unclosed", "{ unclosed")]
+    fn test_render_format(#[case] format: &str, #[case] result: &str) {
+        assert_eq!(render_format(format, &[("foo", "bar")]), result);
+    }
+
+    #[rstest]
+    #[case(0.0, "0")]
+    #[case(8.0, "8")]
+    #[case(12.2, "12.2")]
+    #[case(3.0 * 12.2, "36.6")]
+    #[case(20.0 * 1.22, "24.4")]
+    #[case(0.25, "0.25")]
+    #[case(1_234.567_89, "1234.57")]
+    fn test_format_svg_number(#[case] value: f64, #[case] result: &str) {
+        assert_eq!(format_svg_number(value), result);
+    }
+
+    #[test]
+    fn test_make_svg_tag() {
+        assert_eq!(
+            make_svg_tag("rect", None, &[("x", "1".into()), ("fill", "#fff".into())]),
+            "<rect x=\"1\" fill=\"#fff\"/>"
+        );
+        assert_eq!(
+            make_svg_tag("text", Some("foo"), &[("x", "1".into())]),
+            "<text x=\"1\">foo</text>"
+        );
+    }
+
+    #[rstest]
+    #[case(b"", 1)]
+    #[case(b"a", 0x0062_0062)]
+    #[case(b"Wikipedia", 0x11e6_0398)]
+    fn test_adler32(#[case] data: &[u8], #[case] checksum: u32) {
+        assert_eq!(adler32(data), checksum);
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">Tom & 'Jerry'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#x27;Jerry&#x27;&lt;/a&gt;"
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use crate::color_triplet::ColorTriplet;
+
+/// The color space that a [`Gradient`] interpolates in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum GradientSpace {
+    /// Blend the red, green and blue components, as Rich does.
+    #[default]
+    Rgb,
+    /// Mix in OKLab, which avoids the dull and dark midpoints of RGB blends.
+    Oklab,
+}
+
+/// A smooth transition through a number of color stops.
+///
+/// The stops are spaced evenly, from position 0 for the first stop to position
+/// 1 for the last.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Gradient {
+    stops: Vec<ColorTriplet>,
+    space: GradientSpace,
+}
+
+impl Gradient {
+    /// Instantiate a new [`Gradient`] that blends in RGB.
+    ///
+    /// This is a convenience for [`Gradient::try_new`] when the stops are known
+    /// to be non-empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `stops` - The colors to pass through, in order.
+    ///
+    /// # Panics
+    ///
+    /// If there are no stops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    /// use wealthy::gradient::Gradient;
+    ///
+    /// let gradient = Gradient::new([ColorTriplet::new(255, 0, 0), ColorTriplet::new(0, 0, 255)]);
+    ///
+    /// assert_eq!(gradient.sample(0.5), ColorTriplet::new(127, 0, 127));
+    /// ```
+    pub fn new(stops: impl Into<Vec<ColorTriplet>>) -> Self {
+        Self::try_new(stops).expect("a gradient needs at least one color stop")
+    }
+
+    /// Instantiate a new [`Gradient`] that blends in RGB, if there is at least
+    /// one stop.
+    ///
+    /// # Arguments
+    ///
+    /// * `stops` - The colors to pass through, in order.
+    ///
+    /// # Returns
+    ///
+    /// The gradient, or `None` if there are no stops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    /// use wealthy::gradient::Gradient;
+    ///
+    /// assert!(Gradient::try_new([ColorTriplet::new(255, 0, 0)]).is_some());
+    /// assert!(Gradient::try_new([]).is_none());
+    /// ```
+    pub fn try_new(stops: impl Into<Vec<ColorTriplet>>) -> Option<Self> {
+        let stops = stops.into();
+
+        (!stops.is_empty()).then(|| Self {
+            stops,
+            space: GradientSpace::default(),
+        })
+    }
+
+    /// Set the color space to interpolate in.
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - The color space.
+    #[must_use]
+    pub const fn with_space(mut self, space: GradientSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Get the color stops.
+    pub fn stops(&self) -> &[ColorTriplet] { &self.stops }
+
+    /// Get the color space the gradient interpolates in.
+    pub const fn space(&self) -> GradientSpace { self.space }
+
+    /// Get the color at a position along the gradient.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position, between 0 (the first stop) and 1 (the last
+    ///   stop). Positions outside of this range are clamped.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn sample(&self, position: f64) -> ColorTriplet {
+        if self.stops.len() == 1 {
+            return self.stops[0];
+        }
+
+        let scaled = position.clamp(0.0, 1.0) * (self.stops.len() - 1) as f64;
+        let index = (scaled as usize).min(self.stops.len() - 2);
+        let cross_fade = scaled - index as f64;
+        let (start, end) = (&self.stops[index], &self.stops[index + 1]);
+
+        match self.space {
+            GradientSpace::Rgb => start.blend(end, cross_fade),
+            GradientSpace::Oklab => start.mix(end, cross_fade),
+        }
+    }
+
+    /// Get evenly spaced colors along the gradient, including both ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of colors to get.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    /// use wealthy::gradient::Gradient;
+    ///
+    /// let gradient = Gradient::new([ColorTriplet::new(0, 0, 0), ColorTriplet::new(200, 100, 0)]);
+    ///
+    /// assert_eq!(
+    ///     gradient.colors(3),
+    ///     [
+    ///         ColorTriplet::new(0, 0, 0),
+    ///         ColorTriplet::new(100, 50, 0),
+    ///         ColorTriplet::new(200, 100, 0)
+    ///     ]
+    /// );
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    pub fn colors(&self, count: usize) -> Vec<ColorTriplet> {
+        match count {
+            0 => vec![],
+            1 => vec![self.sample(0.0)],
+            _ => (0..count)
+                .map(|index| self.sample(index as f64 / (count - 1) as f64))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn red_green_blue() -> Gradient {
+        Gradient::new([
+            ColorTriplet::new(255, 0, 0),
+            ColorTriplet::new(0, 255, 0),
+            ColorTriplet::new(0, 0, 255),
+        ])
+    }
+
+    #[rstest]
+    #[case(0.0, ColorTriplet::new(255, 0, 0))]
+    #[case(0.25, ColorTriplet::new(127, 127, 0))]
+    #[case(0.5, ColorTriplet::new(0, 255, 0))]
+    #[case(0.75, ColorTriplet::new(0, 127, 127))]
+    #[case(1.0, ColorTriplet::new(0, 0, 255))]
+    #[case(-1.0, ColorTriplet::new(255, 0, 0))]
+    #[case(2.0, ColorTriplet::new(0, 0, 255))]
+    fn test_sample(#[case] position: f64, #[case] result: ColorTriplet) {
+        assert_eq!(red_green_blue().sample(position), result);
+    }
+
+    #[test]
+    fn test_sample_oklab() {
+        let gradient = red_green_blue().with_space(GradientSpace::Oklab);
+
+        assert_eq!(gradient.space(), GradientSpace::Oklab);
+        assert_eq!(gradient.sample(0.0), ColorTriplet::new(255, 0, 0));
+        assert_eq!(gradient.sample(0.5), ColorTriplet::new(0, 255, 0));
+        assert_eq!(
+            gradient.sample(0.25),
+            ColorTriplet::new(255, 0, 0).mix(&ColorTriplet::new(0, 255, 0), 0.5)
+        );
+    }
+
+    #[test]
+    fn test_single_stop() {
+        let gradient = Gradient::new([ColorTriplet::new(1, 2, 3)]);
+
+        assert_eq!(gradient.colors(2), [ColorTriplet::new(1, 2, 3); 2]);
+    }
+
+    #[rstest]
+    #[case(0, vec![])]
+    #[case(1, vec![ColorTriplet::new(255, 0, 0)])]
+    #[case(3, vec![
+        ColorTriplet::new(255, 0, 0),
+        ColorTriplet::new(0, 255, 0),
+        ColorTriplet::new(0, 0, 255),
+    ])]
+    fn test_colors(#[case] count: usize, #[case] result: Vec<ColorTriplet>) {
+        assert_eq!(red_green_blue().colors(count), result);
+    }
+
+    #[test]
+    #[should_panic(expected = "a gradient needs at least one color stop")]
+    fn test_no_stops() { Gradient::new([]); }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Gradient::try_new([]), None);
+        assert_eq!(
+            Gradient::try_new([ColorTriplet::new(1, 2, 3)]),
+            Some(Gradient::new([ColorTriplet::new(1, 2, 3)]))
+        );
+    }
+}
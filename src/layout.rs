@@ -0,0 +1,523 @@
+use std::fmt::{self, Debug};
+use std::iter::zip;
+use std::ops::{Index, IndexMut};
+
+use crate::console::{Console, ConsoleOptions, Renderable};
+use crate::ratio::{ratio_resolve, Edge, HasEdge};
+use crate::region::Region;
+use crate::segment::Segment;
+
+/// How a [`Layout`] divides its region between its children.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Splitter {
+    /// Side by side, dividing the width.
+    Row,
+    /// Stacked on top of each other, dividing the height.
+    #[default]
+    Column,
+}
+
+impl Splitter {
+    /// Divide a region amongst several child layouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `children` - The child layouts.
+    /// * `region` - The region to divide.
+    ///
+    /// # Returns
+    ///
+    /// The region of each child.
+    fn divide(self, children: &[&Layout<'_>], region: Region) -> Vec<Region> {
+        let Region {
+            x,
+            y,
+            width,
+            height,
+        } = region;
+        let mut offset = 0;
+
+        match self {
+            Self::Row => ratio_resolve(width, children)
+                .into_iter()
+                .map(|child_width| {
+                    let region = Region::new(x + offset, y, child_width, height);
+                    offset += child_width;
+                    region
+                })
+                .collect(),
+            Self::Column => ratio_resolve(height, children)
+                .into_iter()
+                .map(|child_height| {
+                    let region = Region::new(x, y + offset, width, child_height);
+                    offset += child_height;
+                    region
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A renderable to divide a fixed height in to rows or columns.
+///
+/// A layout without children renders its renderable, or a placeholder showing
+/// its name and size if it has none.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::layout::Layout;
+/// use wealthy::region::Region;
+///
+/// let mut layout = Layout::new();
+/// layout.split_column([
+///     Layout::new().with_name("header").with_size(3),
+///     Layout::new().with_name("main"),
+/// ]);
+/// layout["main"].split_row([
+///     Layout::new().with_name("side"),
+///     Layout::new().with_name("body").with_ratio(2),
+/// ]);
+/// layout["body"].update("Hello, World!");
+///
+/// let regions = layout.regions(60, 20);
+/// let region = |name| {
+///     regions
+///         .iter()
+///         .find(|(layout, _)| layout.name.as_deref() == Some(name))
+/// };
+///
+/// assert_eq!(region("header").unwrap().1, Region::new(0, 0, 60, 3));
+/// assert_eq!(region("side").unwrap().1, Region::new(0, 3, 20, 17));
+/// assert_eq!(region("body").unwrap().1, Region::new(20, 3, 40, 17));
+/// ```
+pub struct Layout<'a> {
+    renderable: Option<Box<dyn Renderable + 'a>>,
+    /// Identifier of the layout, used by [`Layout::get`].
+    pub name: Option<String>,
+    /// Fixed size of the layout, or `None` for a flexible size.
+    pub size: Option<u32>,
+    /// Minimum size of a flexible layout.
+    pub minimum_size: u32,
+    /// Share of the space left over by fixed size layouts.
+    pub ratio: u32,
+    /// Show the layout (or not).
+    pub visible: bool,
+    splitter: Splitter,
+    children: Vec<Layout<'a>>,
+}
+
+impl Default for Layout<'_> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<'a> Layout<'a> {
+    /// Instantiate a new [`Layout`] with a placeholder renderable.
+    pub fn new() -> Self {
+        Self {
+            renderable: None,
+            name: None,
+            size: None,
+            minimum_size: 1,
+            ratio: 1,
+            visible: true,
+            splitter: Splitter::default(),
+            children: vec![],
+        }
+    }
+
+    /// Set the renderable shown in the layout.
+    #[must_use]
+    pub fn with_renderable(mut self, renderable: impl Renderable + 'a) -> Self {
+        self.update(renderable);
+        self
+    }
+
+    /// Set the name of the layout.
+    #[must_use]
+    pub fn with_name(self, name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Give the layout a fixed size.
+    #[must_use]
+    pub fn with_size(self, size: u32) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    /// Set the minimum size of a flexible layout.
+    #[must_use]
+    pub fn with_minimum_size(self, minimum_size: u32) -> Self {
+        Self {
+            minimum_size,
+            ..self
+        }
+    }
+
+    /// Set the share of the space left over by fixed size layouts.
+    #[must_use]
+    pub fn with_ratio(self, ratio: u32) -> Self { Self { ratio, ..self } }
+
+    /// Show the layout (or not).
+    #[must_use]
+    pub fn with_visible(self, visible: bool) -> Self { Self { visible, ..self } }
+
+    /// Replace the renderable shown in the layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderable` - The new renderable.
+    pub fn update(&mut self, renderable: impl Renderable + 'a) {
+        self.renderable = Some(Box::new(renderable));
+    }
+
+    /// Get how the layout divides its region between its children.
+    pub const fn splitter(&self) -> Splitter { self.splitter }
+
+    /// Get the visible children of the layout.
+    pub fn children(&self) -> Vec<&Self> {
+        self.children.iter().filter(|child| child.visible).collect()
+    }
+
+    /// Split the layout in to multiple sub-layouts, replacing any existing
+    /// ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `layouts` - The sub-layouts.
+    /// * `splitter` - How to divide the layout between them.
+    pub fn split(&mut self, layouts: impl IntoIterator<Item = Self>, splitter: Splitter) {
+        self.splitter = splitter;
+        self.children = layouts.into_iter().collect();
+    }
+
+    /// Split the layout in to a row (layouts side by side).
+    ///
+    /// # Arguments
+    ///
+    /// * `layouts` - The sub-layouts.
+    pub fn split_row(&mut self, layouts: impl IntoIterator<Item = Self>) {
+        self.split(layouts, Splitter::Row);
+    }
+
+    /// Split the layout in to a column (layouts stacked on top of each other).
+    ///
+    /// # Arguments
+    ///
+    /// * `layouts` - The sub-layouts.
+    pub fn split_column(&mut self, layouts: impl IntoIterator<Item = Self>) {
+        self.split(layouts, Splitter::Column);
+    }
+
+    /// Add sub-layouts to the existing split.
+    ///
+    /// # Arguments
+    ///
+    /// * `layouts` - The sub-layouts.
+    pub fn add_split(&mut self, layouts: impl IntoIterator<Item = Self>) {
+        self.children.extend(layouts);
+    }
+
+    /// Remove all the sub-layouts.
+    pub fn unsplit(&mut self) { self.children.clear(); }
+
+    /// Get a named layout, searching this layout and its descendants.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the layout.
+    pub fn get(&self, name: &str) -> Option<&Self> {
+        if self.name.as_deref() == Some(name) {
+            return Some(self);
+        }
+
+        self.children.iter().find_map(|child| child.get(name))
+    }
+
+    /// Get a mutable reference to a named layout, searching this layout and
+    /// its descendants.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the layout.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Self> {
+        if self.name.as_deref() == Some(name) {
+            return Some(self);
+        }
+
+        self.children
+            .iter_mut()
+            .find_map(|child| child.get_mut(name))
+    }
+
+    /// Get the region of this layout and each of its visible descendants.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Width of the whole layout.
+    /// * `height` - Height of the whole layout.
+    ///
+    /// # Returns
+    ///
+    /// Layouts and their regions, sorted by region.
+    pub fn regions(&self, width: u32, height: u32) -> Vec<(&Self, Region)> {
+        let mut stack = vec![(self, Region::new(0, 0, width, height))];
+        let mut regions = vec![];
+
+        while let Some((layout, region)) = stack.pop() {
+            let children = layout.children();
+
+            if !children.is_empty() {
+                let child_regions = layout.splitter.divide(&children, region);
+                stack.extend(zip(children, child_regions));
+            }
+            regions.push((layout, region));
+        }
+        regions.sort_by_key(|&(_, region)| region);
+
+        regions
+    }
+}
+
+impl Debug for Layout<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layout")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .field("minimum_size", &self.minimum_size)
+            .field("ratio", &self.ratio)
+            .field("visible", &self.visible)
+            .field("splitter", &self.splitter)
+            .field("children", &self.children)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Index<&str> for Layout<'a> {
+    type Output = Layout<'a>;
+
+    /// Get a named layout.
+    ///
+    /// # Panics
+    ///
+    /// If there is no layout called `name`.
+    fn index(&self, name: &str) -> &Self::Output {
+        self.get(name)
+            .unwrap_or_else(|| panic!("no layout with name {name:?}"))
+    }
+}
+
+impl IndexMut<&str> for Layout<'_> {
+    /// Get a named layout.
+    ///
+    /// # Panics
+    ///
+    /// If there is no layout called `name`.
+    fn index_mut(&mut self, name: &str) -> &mut Self::Output {
+        self.get_mut(name)
+            .unwrap_or_else(|| panic!("no layout with name {name:?}"))
+    }
+}
+
+impl HasEdge for Layout<'_> {
+    fn protocol(&self) -> Edge {
+        Edge {
+            size: self.size,
+            ratio: self.ratio,
+            minimum_size: self.minimum_size,
+        }
+    }
+}
+
+impl Renderable for Layout<'_> {
+    fn render(&self, console: &Console<'_>, options: &ConsoleOptions) -> Vec<Segment> {
+        let width = options.max_width;
+        let height = options.height.unwrap_or_else(|| console.height());
+        let mut layout_lines: Vec<Vec<Segment>> = vec![vec![]; height as usize];
+
+        for (layout, region) in self.regions(width, height) {
+            if !layout.children().is_empty() {
+                continue;
+            }
+
+            let options = options.update_dimensions(region.width, region.height);
+            let lines = match &layout.renderable {
+                Some(renderable) => {
+                    console.render_lines(renderable.as_ref(), Some(&options), None, true, false)
+                },
+                None => console.render_lines(
+                    &Placeholder(layout.name.as_deref()),
+                    Some(&options),
+                    None,
+                    true,
+                    false,
+                ),
+            };
+
+            for (row, line) in zip(
+                layout_lines
+                    .iter_mut()
+                    .skip(region.y as usize)
+                    .take(region.height as usize),
+                lines,
+            ) {
+                row.extend(line);
+            }
+        }
+
+        layout_lines
+            .into_iter()
+            .flat_map(|line| line.into_iter().chain([Segment::line()]))
+            .collect()
+    }
+}
+
+/// Shows the name and size of a layout without a renderable.
+struct Placeholder<'a>(Option<&'a str>);
+
+impl Renderable for Placeholder<'_> {
+    fn render(&self, _console: &Console<'_>, options: &ConsoleOptions) -> Vec<Segment> {
+        let width = options.max_width;
+        let height = options.height.unwrap_or(options.size.height);
+        let title = match self.0 {
+            Some(name) => format!("{name:?} ({width} x {height})"),
+            None => format!("({width} x {height})"),
+        };
+
+        vec![Segment::new(title, None), Segment::line()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::text::Text;
+
+    fn render(layout: &Layout<'_>, width: u32, height: u32) -> String {
+        let console = Console::new()
+            .with_file(io::sink())
+            .with_width(width)
+            .with_height(height);
+
+        console
+            .render(layout, None)
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect()
+    }
+
+    fn dashboard() -> Layout<'static> {
+        let mut layout = Layout::new();
+        layout.split_column([
+            Layout::new().with_name("header").with_size(3),
+            Layout::new().with_name("main").with_ratio(1),
+            Layout::new().with_name("footer").with_size(10),
+        ]);
+        layout["main"].split_row([
+            Layout::new().with_name("side"),
+            Layout::new().with_name("body").with_ratio(2),
+        ]);
+
+        layout
+    }
+
+    #[rstest]
+    #[case("header", Region::new(0, 0, 80, 3))]
+    #[case("main", Region::new(0, 3, 80, 12))]
+    #[case("side", Region::new(0, 3, 26, 12))]
+    #[case("body", Region::new(26, 3, 54, 12))]
+    #[case("footer", Region::new(0, 15, 80, 10))]
+    fn test_regions(#[case] name: &str, #[case] region: Region) {
+        let layout = dashboard();
+        let regions = layout.regions(80, 25);
+
+        assert_eq!(regions.len(), 6);
+        assert!(regions.iter().any(
+            |(layout, layout_region)| layout.name.as_deref() == Some(name)
+                && *layout_region == region
+        ));
+    }
+
+    #[test]
+    fn test_regions_sorted() {
+        let layout = dashboard();
+        let regions: Vec<_> = layout
+            .regions(80, 25)
+            .into_iter()
+            .map(|(_, region)| region)
+            .collect();
+
+        assert!(regions.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_render() {
+        let mut layout = Layout::new();
+        layout.split_row([
+            Layout::new().with_renderable("foo"),
+            Layout::new().with_renderable(Text::from("bar baz")),
+        ]);
+
+        assert_eq!(render(&layout, 10, 2), "foo  bar  \n     baz  \n");
+    }
+
+    #[test]
+    fn test_render_placeholder() {
+        let mut layout = Layout::new();
+        layout.split_column([Layout::new().with_name("top").with_size(1), Layout::new()]);
+
+        assert_eq!(
+            render(&layout, 20, 3),
+            "\"top\" (20 x 1)      \n(20 x 2)            \n                    \n"
+        );
+    }
+
+    #[test]
+    fn test_visible() {
+        let mut layout = Layout::new();
+        layout.split_row([
+            Layout::new().with_renderable("a"),
+            Layout::new().with_name("hidden").with_visible(false),
+            Layout::new().with_renderable("b"),
+        ]);
+
+        assert_eq!(layout.children().len(), 2);
+        assert_eq!(render(&layout, 4, 1), "a b \n");
+
+        layout["hidden"].visible = true;
+        layout["hidden"].update("c");
+
+        assert_eq!(render(&layout, 6, 1), "a c b \n");
+    }
+
+    #[test]
+    fn test_split() {
+        let mut layout = dashboard();
+
+        assert_eq!(layout.splitter(), Splitter::Column);
+        assert_eq!(layout["main"].splitter(), Splitter::Row);
+        assert!(layout.get("body").is_some());
+        assert!(layout.get("nope").is_none());
+
+        layout["main"].add_split([Layout::new().with_name("extra")]);
+        assert_eq!(layout["main"].children().len(), 3);
+
+        layout["main"].unsplit();
+        assert!(layout.get("body").is_none());
+        assert_eq!(layout.regions(80, 25).len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "no layout with name \"nope\"")]
+    fn test_index_missing() { let _ = &dashboard()["nope"]; }
+}
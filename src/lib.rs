@@ -1,20 +1,34 @@
 #![allow(clippy::must_use_candidate)]
 
 mod cell_widths;
-mod color_triplet;
+mod color_names;
 mod emoji_codes;
 mod emoji_replace;
 mod export_format;
 mod filesize;
 mod palette;
 mod palettes;
-mod ratio;
-mod region;
-mod terminal_theme;
+#[cfg(feature = "theme-files")]
+mod theme_files;
+mod wrap;
 
 pub mod cells;
+pub mod color;
+pub mod color_triplet;
+pub mod console;
+pub mod constraint;
+pub mod gradient;
+pub mod layout;
+pub mod markup;
 pub mod measure;
+pub mod ratio;
+pub mod region;
+pub mod rule;
+pub mod segment;
 // TODO: Re-export it as wealthy::progress::Spinners;
 pub mod spinners;
+pub mod style;
+pub mod terminal_theme;
+pub mod text;
 
 pub use spinners::names as Spinners;
@@ -0,0 +1,384 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use crate::emoji_replace::emoji_replace;
+use crate::style::{Style, StyleSyntaxError};
+use crate::text::{Span, Text};
+
+lazy_static! {
+    static ref RE_TAGS: Regex = Regex::new(r#"((\\*)\[([a-z#/@][^\[]*?)\])"#).unwrap();
+    static ref RE_ESCAPE: Regex = Regex::new(r#"(\\*)(\[[a-z#/@][^\[]*?\])"#).unwrap();
+}
+
+/// The markup could not be rendered.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MarkupError {
+    /// A closing tag doesn't match any open tag.
+    UnmatchedClosingTag {
+        /// The closing tag.
+        tag: String,
+        /// Byte offset of the tag in the markup.
+        position: usize,
+    },
+    /// An implicit closing tag (`[/]`) was found with no open tags.
+    NothingToClose {
+        /// Byte offset of the tag in the markup.
+        position: usize,
+    },
+    /// The contents of an opening tag isn't a valid style.
+    InvalidStyle {
+        /// The opening tag.
+        tag: String,
+        /// Byte offset of the tag in the markup.
+        position: usize,
+        /// Why the style is invalid.
+        error: StyleSyntaxError,
+    },
+}
+
+impl Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnmatchedClosingTag { tag, position } => write!(
+                f,
+                "closing tag '{tag}' at position {position} doesn't match any open tag"
+            ),
+            Self::NothingToClose { position } => write!(
+                f,
+                "closing tag '[/]' at position {position} has nothing to close"
+            ),
+            Self::InvalidStyle {
+                tag,
+                position,
+                error,
+            } => write!(f, "tag '{tag}' at position {position} is invalid; {error}"),
+        }
+    }
+}
+
+impl Error for MarkupError {}
+
+/// A tag in console markup.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Tag {
+    /// The tag name. e.g. 'bold'.
+    name: String,
+    /// Any additional parameters after the name.
+    parameters: Option<String>,
+}
+
+impl Tag {
+    /// Get the string representation of this tag.
+    fn markup(&self) -> String {
+        match &self.parameters {
+            Some(parameters) => format!("[{}={parameters}]", self.name),
+            None => format!("[{}]", self.name),
+        }
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.parameters {
+            Some(parameters) => write!(f, "{} {parameters}", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Escapes text so that it won't be interpreted as markup.
+///
+/// # Arguments
+///
+/// * `markup` - Content to be inserted in to markup.
+///
+/// # Returns
+///
+/// Markup with square brackets escaped.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::markup::escape;
+///
+/// assert_eq!(escape("foo[bar]"), r"foo\[bar]");
+/// assert_eq!(escape(r"C:\"), r"C:\\");
+/// ```
+pub fn escape(markup: &str) -> String {
+    let markup = RE_ESCAPE.replace_all(markup, |captures: &Captures| {
+        let (backslashes, text) = (&captures[1], &captures[2]);
+
+        format!("{backslashes}{backslashes}\\{text}")
+    });
+
+    if markup.ends_with('\\') && !markup.ends_with("\\\\") {
+        return format!("{markup}\\");
+    }
+
+    markup.into_owned()
+}
+
+/// Parse markup in to an iterable of (position, text, tag).
+fn parse(markup: &str) -> Vec<(usize, Option<String>, Option<Tag>)> {
+    let mut parsed = vec![];
+    let mut position = 0;
+
+    for captures in RE_TAGS.captures_iter(markup) {
+        let full_text = captures.get(1).unwrap();
+        let escapes = &captures[2];
+        let tag_text = &captures[3];
+        let (mut start, end) = (full_text.start(), full_text.end());
+
+        if start > position {
+            parsed.push((start, Some(markup[position..start].into()), None));
+        }
+
+        if !escapes.is_empty() {
+            let (backslashes, escaped) = (escapes.len() / 2, escapes.len() % 2);
+
+            if backslashes > 0 {
+                // Literal backslashes.
+                parsed.push((start, Some("\\".repeat(backslashes)), None));
+                start += backslashes * 2;
+            }
+            if escaped > 0 {
+                // Escape of tag.
+                parsed.push((
+                    start,
+                    Some(full_text.as_str()[escapes.len()..].into()),
+                    None,
+                ));
+                position = end;
+                continue;
+            }
+        }
+
+        let tag = match tag_text.split_once('=') {
+            Some((name, parameters)) => Tag {
+                name: name.into(),
+                parameters: Some(parameters.into()),
+            },
+            None => Tag {
+                name: tag_text.into(),
+                parameters: None,
+            },
+        };
+        parsed.push((start, None, Some(tag)));
+        position = end;
+    }
+
+    if position < markup.len() {
+        parsed.push((position, Some(markup[position..].into()), None));
+    }
+
+    parsed
+}
+
+/// Normalize a style name, so that equivalent tags can be matched.
+fn normalize(style_name: &str) -> String {
+    Style::parse(style_name).map_or_else(
+        |_| style_name.trim().to_lowercase(),
+        |style| style.to_string(),
+    )
+}
+
+/// Render console markup in to a [`Text`] instance.
+///
+/// Emoji codes such as `:smiley:` are replaced by the emoji.
+///
+/// # Arguments
+///
+/// * `markup` - A string containing console markup.
+///
+/// # Errors
+///
+/// [`MarkupError`] if there is a syntax error in the markup.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::markup::render;
+/// use wealthy::style::Style;
+/// use wealthy::text::Span;
+///
+/// let text = render("[bold red]Hello[/] World").unwrap();
+///
+/// assert_eq!(text.plain(), "Hello World");
+/// assert_eq!(
+///     text.spans,
+///     [Span::new(0, 5, Style::parse("bold red").unwrap())]
+/// );
+/// ```
+pub fn render(markup: &str) -> Result<Text, MarkupError> {
+    if !markup.contains('[') {
+        return Ok(Text::from(emoji_replace(markup, None, None).into_owned()));
+    }
+
+    let mut text = Text::from("");
+    // Text offset, markup position and tag of the open tags.
+    let mut style_stack: Vec<(usize, usize, Tag)> = vec![];
+    let mut spans = vec![];
+
+    for (position, plain_text, tag) in parse(markup) {
+        if let Some(plain_text) = plain_text {
+            let plain_text = plain_text.replace("\\[", "[");
+            text.append(&emoji_replace(&plain_text, None, None), None);
+        } else if let Some(tag) = tag {
+            if let Some(style_name) = tag.name.strip_prefix('/') {
+                // Closing tag.
+                let style_name = style_name.trim();
+
+                let (start, open_position, open_tag) = if style_name.is_empty() {
+                    // Implicit close.
+                    style_stack
+                        .pop()
+                        .ok_or(MarkupError::NothingToClose { position })?
+                } else {
+                    // Explicit close.
+                    let style_name = normalize(style_name);
+                    let index = style_stack
+                        .iter()
+                        .rposition(|(_, _, open_tag)| open_tag.name == style_name)
+                        .ok_or_else(|| MarkupError::UnmatchedClosingTag {
+                            tag: tag.markup(),
+                            position,
+                        })?;
+
+                    style_stack.remove(index)
+                };
+
+                spans.push((start, text.len(), open_position, open_tag));
+            } else {
+                // Opening tag.
+                let tag = Tag {
+                    name: normalize(&tag.name),
+                    parameters: tag.parameters,
+                };
+                style_stack.push((text.len(), position, tag));
+            }
+        }
+    }
+
+    while let Some((start, position, tag)) = style_stack.pop() {
+        spans.push((start, text.len(), position, tag));
+    }
+
+    spans.reverse();
+    spans.sort_by_key(|(start, ..)| *start);
+
+    text.spans = spans
+        .into_iter()
+        .map(|(start, end, position, tag)| {
+            Style::parse(&tag.to_string())
+                .map(|style| Span::new(start, end, style))
+                .map_err(|error| MarkupError::InvalidStyle {
+                    tag: tag.markup(),
+                    position,
+                    error,
+                })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn span(start: usize, end: usize, style: &str) -> Span {
+        Span::new(start, end, Style::parse(style).unwrap())
+    }
+
+    #[rstest]
+    #[case("foo[bar]", r"foo\[bar]")]
+    #[case(r"foo\[bar]", r"foo\\\[bar]")]
+    #[case("[5]", "[5]")]
+    #[case(r"\[5]", r"\[5]")]
+    #[case(r"C:\", r"C:\\")]
+    fn test_escape(#[case] markup: &str, #[case] result: &str) {
+        assert_eq!(escape(markup), result);
+    }
+
+    #[test]
+    fn test_parse() {
+        let tag = |name: &str| {
+            Some(Tag {
+                name: name.into(),
+                parameters: None,
+            })
+        };
+
+        assert_eq!(
+            parse(r"[foo]hello[/foo][bar]world[/]\[escaped]"),
+            [
+                (0, None, tag("foo")),
+                (10, Some("hello".into()), None),
+                (10, None, tag("/foo")),
+                (16, None, tag("bar")),
+                (26, Some("world".into()), None),
+                (26, None, tag("/")),
+                (29, Some("[escaped]".into()), None),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("[bold]FOO[/bold]", "FOO", &[span(0, 3, "bold")])]
+    #[case(r#"[[1], [1,2,3,4], ["hello"], [None], [False], [True]] []"#, r#"[[1], [1,2,3,4], ["hello"], [None], [False], [True]] []"#, &[])]
+    #[case("[link=foo]FOO[/link]", "FOO", &[span(0, 3, "link foo")])]
+    #[case("[green]X[blue]Y[/blue]Z[/green]", "XYZ", &[span(0, 3, "green"), span(1, 2, "blue")])]
+    #[case("[green]X[bold]Y[/green]Z[/bold]", "XYZ", &[span(0, 2, "green"), span(1, 3, "bold")])]
+    #[case("[red][blue]B[/blue]R[/red]", "BR", &[span(0, 2, "red"), span(0, 1, "blue")])]
+    #[case("[bold]X[/]Y", "XY", &[span(0, 1, "bold")])]
+    #[case("[green]X[bold]Y[/]Z[/]", "XYZ", &[span(0, 3, "green"), span(1, 2, "bold")])]
+    #[case("[bold  red]X[/red bold]", "X", &[span(0, 1, "bold red")])]
+    #[case("[bold]X", "X", &[span(0, 1, "bold")])]
+    #[case("[dim white][url=[/]", "[url=", &[span(0, 5, "dim white")])]
+    #[case(":smiley: [bold]:atm_sign:[/]", "😃 🏧", &[span(5, 9, "bold")])]
+    #[case(":smiley: and :smiley:", "😃 and 😃", &[])]
+    #[case("[bold]:smiley: :thumbs_up:[/]", "😃 👍", &[span(0, 9, "bold")])]
+    fn test_render(#[case] markup: &str, #[case] plain: &str, #[case] spans: &[Span]) {
+        let text = render(markup).unwrap();
+
+        assert_eq!(text.plain(), plain);
+        assert_eq!(text.spans, spans);
+    }
+
+    #[rstest]
+    #[case(r"\\[bold]FOO", r"\FOO")]
+    #[case(r"\[bold]FOO", "[bold]FOO")]
+    #[case(r"\\[bold]some text", r"\some text")]
+    #[case(r"\\\[bold]some text", r"\[bold]some text")]
+    #[case(r"\\", r"\\")]
+    #[case(r"\\\\", r"\\\\")]
+    #[case(&format!("[red]{}[/red]", escape(r"C:\")), r"C:\")]
+    fn test_render_escape(#[case] markup: &str, #[case] plain: &str) {
+        assert_eq!(render(markup).unwrap().plain(), plain);
+    }
+
+    #[rstest]
+    #[case("foo[/]", MarkupError::NothingToClose { position: 3 })]
+    #[case("foo[/bar]", MarkupError::UnmatchedClosingTag { tag: "[/bar]".into(), position: 3 })]
+    #[case("[bold]hello[/red]", MarkupError::UnmatchedClosingTag { tag: "[/red]".into(), position: 11 })]
+    fn test_markup_error(#[case] markup: &str, #[case] error: MarkupError) {
+        assert_eq!(render(markup), Err(error));
+    }
+
+    #[test]
+    fn test_invalid_style() {
+        assert!(matches!(
+            render("foo [bar]baz[/bar]"),
+            Err(MarkupError::InvalidStyle { position: 4, .. })
+        ));
+        assert_eq!(
+            render("foo[/]").unwrap_err().to_string(),
+            "closing tag '[/]' at position 3 has nothing to close"
+        );
+    }
+}
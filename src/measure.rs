@@ -1,5 +1,4 @@
-// TODO: Port `measure_renderables` when `Console`, `ConsoleOptions` and
-// `RederableType` are ported.
+use crate::console::{Console, ConsoleOptions, Renderable};
 
 /// Stores the minimum and maximum widths (in characters) required to render an
 /// object.
@@ -11,11 +10,61 @@ pub struct Measurement {
     pub maximum: u32,
 }
 
-// TODO: Port `get` when `Console`, `ConsoleOptions`, `RenderableType` are
-// ported.
 impl Measurement {
     pub const fn new(minimum: u32, maximum: u32) -> Self { Self { minimum, maximum } }
 
+    /// Get a measurement for a renderable.
+    ///
+    /// Renderables which don't implement [`Renderable::measure`] may take up
+    /// to the full width available.
+    ///
+    /// # Arguments
+    ///
+    /// * `console` - Console instance.
+    /// * `options` - Console options.
+    /// * `renderable` - An object that may be rendered.
+    ///
+    /// # Returns
+    ///
+    /// Measurement object containing range of character widths required to
+    /// render the object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::console::Console;
+    /// use wealthy::measure::Measurement;
+    ///
+    /// let console = Console::new().with_width(80);
+    ///
+    /// assert_eq!(
+    ///     Measurement::get(&console, &console.options(), "foo bar"),
+    ///     Measurement::new(3, 7)
+    /// );
+    /// ```
+    pub fn get(
+        console: &Console<'_>,
+        options: &ConsoleOptions,
+        renderable: &(impl Renderable + ?Sized),
+    ) -> Self {
+        let max_width = options.max_width;
+        if max_width < 1 {
+            return Self::new(0, 0);
+        }
+
+        match renderable.measure(console, options) {
+            Some(measurement) => {
+                let render_width = measurement.normalize().with_maximum(max_width);
+                if render_width.maximum < 1 {
+                    return Self::new(0, 0);
+                }
+
+                render_width.normalize()
+            },
+            None => Self::new(0, max_width),
+        }
+    }
+
     /// Get difference between maximum and minimum.
     ///
     /// # Examples
@@ -133,14 +182,112 @@ impl Measurement {
     }
 }
 
-// TODO: Port `test_no_renderable` when `Console` and `Text`, and `get` are
-// ported. TODO: Port `test_measure_renderables` when `Console`, and
-// `measure_renderables` are ported.
+/// Get a measurement that would fit a number of renderables.
+///
+/// # Arguments
+///
+/// * `console` - Console instance.
+/// * `options` - Console options.
+/// * `renderables` - One or more renderable objects.
+///
+/// # Returns
+///
+/// Measurement object containing range of character widths required to
+/// contain all given renderables.
+pub fn measure_renderables(
+    console: &Console<'_>,
+    options: &ConsoleOptions,
+    renderables: &[&dyn Renderable],
+) -> Measurement {
+    if renderables.is_empty() {
+        return Measurement::new(0, 0);
+    }
+
+    let measurements: Vec<Measurement> = renderables
+        .iter()
+        .map(|renderable| Measurement::get(console, options, *renderable))
+        .collect();
+
+    Measurement::new(
+        measurements
+            .iter()
+            .map(|measurement| measurement.minimum)
+            .max()
+            .unwrap_or(0),
+        measurements
+            .iter()
+            .map(|measurement| measurement.maximum)
+            .max()
+            .unwrap_or(0),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
 
     use super::*;
+    use crate::segment::Segment;
+
+    struct Unmeasured;
+
+    impl Renderable for Unmeasured {
+        fn render(&self, _console: &Console<'_>, _options: &ConsoleOptions) -> Vec<Segment> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_no_measure() {
+        let console = Console::new().with_width(20);
+
+        assert_eq!(
+            Measurement::get(&console, &console.options(), &Unmeasured),
+            Measurement::new(0, 20)
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let console = Console::new().with_width(20);
+        let options = console.options();
+
+        assert_eq!(
+            Measurement::get(&console, &options, "foo barbaz\nqux"),
+            Measurement::new(6, 10)
+        );
+        assert_eq!(
+            Measurement::get(&console, &options, &"x".repeat(30)),
+            Measurement::new(20, 20)
+        );
+        assert_eq!(
+            Measurement::get(&console, &options.update_width(0), "foo"),
+            Measurement::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_measure_renderables() {
+        let console = Console::new().with_width(20);
+        let options = console.options();
+
+        assert_eq!(
+            measure_renderables(&console, &options, &[]),
+            Measurement::new(0, 0)
+        );
+        assert_eq!(
+            measure_renderables(&console, &options.update_width(0), &[&"hello"]),
+            Measurement::new(0, 0)
+        );
+        assert_eq!(
+            measure_renderables(
+                &console,
+                &options,
+                &[&"hello world", &"foobar", &Unmeasured]
+            ),
+            Measurement::new(6, 20)
+        );
+    }
 
     #[rstest]
     #[case(Some(10), Some(50), 20, 50)]
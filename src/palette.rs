@@ -4,10 +4,10 @@ use std::ops::Index;
 
 use ordered_float::OrderedFloat;
 
-use crate::color_triplet::ColorTriplet;
+use crate::color_triplet::{ColorDistance, ColorTriplet};
 
 /// A palette of available colors.
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct Palette<const N: usize> {
     pub(crate) colors: [ColorTriplet; N],
 }
@@ -22,9 +22,8 @@ impl<const N: usize> Palette<N> {
     }
 
     // NOTE: This was originally named `match` in Rich.
-    // NOTE: This is inefficient, and needs caching but [`cached`] doesn't support
-    // methods.
-    /// Find a color from a palette that most closely matches a given color.
+    /// Find a color from a palette that most closely matches a given color,
+    /// using the [`ColorDistance::Redmean`] metric.
     ///
     /// # Arguments
     ///
@@ -34,31 +33,70 @@ impl<const N: usize> Palette<N> {
     ///
     /// Index of the closest matching color if found.
     pub(crate) fn closest(&self, color: (u8, u8, u8)) -> Option<usize> {
-        let (red1, green1, blue1) = color;
-
-        let get_color_distance = |index: &usize| -> OrderedFloat<f32> {
-            let ColorTriplet {
-                red: red2,
-                green: green2,
-                blue: blue2,
-            } = self.colors[*index];
-
-            let red_mean = i16::from((red1 + red2) / 2);
-            let red = i16::from(red1) - i16::from(red2);
-            let green = i16::from(green1) - i16::from(green2);
-            let blue = i16::from(blue1) - i16::from(blue2);
-
-            OrderedFloat(
-                f32::from(
-                    (((512 + red_mean) * red * red) >> 8)
-                        + 4 * green * green
-                        + (((767 - red_mean) * blue * blue) >> 8),
-                )
-                .sqrt(),
-            )
-        };
-
-        (0..self.colors.len()).min_by_key(get_color_distance)
+        self.closest_with(color, ColorDistance::Redmean)
+    }
+
+    /// Find a color from a palette that most closely matches a given color.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color triplet to compare with.
+    /// * `metric` - How to measure the distance between colors.
+    ///
+    /// # Returns
+    ///
+    /// Index of the closest matching color if found.
+    pub(crate) fn closest_with(&self, color: (u8, u8, u8), metric: ColorDistance) -> Option<usize> {
+        let color = metric.project(&ColorTriplet::new(color.0, color.1, color.2));
+
+        self.colors
+            .iter()
+            .map(|palette_color| OrderedFloat(metric.between(color, metric.project(palette_color))))
+            .enumerate()
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(index, _)| index)
+    }
+}
+
+/// A palette with every color already projected for one [`ColorDistance`], so
+/// looking up the closest color only has to project the color being matched.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ProjectedPalette {
+    metric: ColorDistance,
+    colors: Vec<(f64, f64, f64)>,
+}
+
+impl ProjectedPalette {
+    /// Instantiate a new [`ProjectedPalette`] from the colors of a [`Palette`].
+    pub(crate) fn new<const N: usize>(palette: &Palette<N>, metric: ColorDistance) -> Self {
+        Self {
+            metric,
+            colors: palette
+                .colors
+                .iter()
+                .map(|color| metric.project(color))
+                .collect(),
+        }
+    }
+
+    /// Find a color from a palette that most closely matches a given color.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color triplet to compare with.
+    ///
+    /// # Returns
+    ///
+    /// Index of the closest matching color if found.
+    pub(crate) fn closest(&self, color: &ColorTriplet) -> Option<usize> {
+        let color = self.metric.project(color);
+
+        self.colors
+            .iter()
+            .map(|palette_color| OrderedFloat(self.metric.between(color, *palette_color)))
+            .enumerate()
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(index, _)| index)
     }
 }
 
@@ -107,4 +145,51 @@ mod tests {
 
         assert_eq!(palette.closest((red, green, blue)).unwrap(), result);
     }
+
+    #[rstest]
+    #[case((255, 255, 255), 1)]
+    #[case((200, 10, 10), 0)]
+    #[case((0, 0, 0), 2)]
+    fn test_closest_large_components(#[case] color: (u8, u8, u8), #[case] result: usize) {
+        let palette = Palette::new([(255, 0, 0), (255, 255, 255), (0, 0, 0)]);
+
+        assert_eq!(palette.closest(color).unwrap(), result);
+    }
+
+    #[rstest]
+    #[case(ColorDistance::Redmean, 0)]
+    #[case(ColorDistance::Cie76, 0)]
+    #[case(ColorDistance::Ciede2000, 1)]
+    #[case(ColorDistance::Oklab, 1)]
+    fn test_closest_with(#[case] metric: ColorDistance, #[case] result: usize) {
+        // Redmean and CIE76 weigh this dark blue as closer to grey than to blue.
+        let palette = Palette::new([(128, 128, 128), (0, 0, 255)]);
+
+        assert_eq!(palette.closest_with((0, 64, 128), metric).unwrap(), result);
+    }
+
+    #[rstest]
+    #[case(ColorDistance::Redmean)]
+    #[case(ColorDistance::Cie76)]
+    #[case(ColorDistance::Ciede2000)]
+    #[case(ColorDistance::Oklab)]
+    fn test_projected_closest(#[case] metric: ColorDistance) {
+        let palette = Palette::new([(0, 0, 0), (128, 128, 128), (0, 0, 255), (255, 255, 255)]);
+        let projected = ProjectedPalette::new(&palette, metric);
+
+        for color in [(0, 64, 128), (200, 10, 30), (250, 250, 240), (5, 5, 5)] {
+            assert_eq!(
+                projected.closest(&ColorTriplet::new(color.0, color.1, color.2)),
+                palette.closest_with(color, metric)
+            );
+        }
+    }
+
+    #[test]
+    fn test_closest_with_empty() {
+        assert_eq!(
+            Palette::new([]).closest_with((0, 0, 0), ColorDistance::Oklab),
+            None
+        );
+    }
 }
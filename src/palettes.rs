@@ -6,7 +6,7 @@ use crate::palette::Palette;
 
 lazy_static! {
     /// Taken from https://en.wikipedia.org/wiki/ANSI_escape_code (Windows 10 column)
-    static ref WINDOWS_PALETTE: Palette<16> = Palette::new([
+    pub(crate) static ref WINDOWS_PALETTE: Palette<16> = Palette::new([
         (12, 12, 12),
         (197, 15, 31),
         (19, 161, 14),
@@ -28,7 +28,7 @@ lazy_static! {
 
 lazy_static! {
     /// The standard ansi colors (including bright variants)
-    static ref STANDARD_PALETTE: Palette<16> = Palette::new([
+    pub(crate) static ref STANDARD_PALETTE: Palette<16> = Palette::new([
         (0, 0, 0),
         (170, 0, 0),
         (0, 170, 0),
@@ -50,7 +50,7 @@ lazy_static! {
 
 lazy_static! {
     /// The 256 color palette
-    static ref EIGHT_BIT_PALETTE: Palette<256> = Palette::new([
+    pub(crate) static ref EIGHT_BIT_PALETTE: Palette<256> = Palette::new([
         (0, 0, 0),
         (128, 0, 0),
         (0, 128, 0),
@@ -1,15 +1,17 @@
-#![allow(dead_code)]
+//! Divide integer totals in to parts based on ratios.
 
+use std::error::Error;
+use std::fmt::{self, Display};
 use std::iter::zip;
 
 use fraction::Fraction;
 
 /// A translation of Rich's `Edge` protocol. Defines an edge (such as a
 /// `Layout`).
-struct Edge {
-    size: Option<u32>,
-    ratio: u32,
-    minimum_size: u32,
+pub(crate) struct Edge {
+    pub(crate) size: Option<u32>,
+    pub(crate) ratio: u32,
+    pub(crate) minimum_size: u32,
 }
 
 impl Default for Edge {
@@ -23,10 +25,14 @@ impl Default for Edge {
 }
 
 /// Trait to mark a struct as having an `Edge`.
-trait HasEdge {
+pub(crate) trait HasEdge {
     fn protocol(&self) -> Edge;
 }
 
+impl<E: HasEdge> HasEdge for &E {
+    fn protocol(&self) -> Edge { (**self).protocol() }
+}
+
 impl HasEdge for Edge {
     fn protocol(&self) -> Self {
         Self {
@@ -45,6 +51,7 @@ impl HasEdge for Edge {
 /// two edges with a minimum size of 20 each and `total` is 30 then the returned
 /// list will be greater than total. In practice, this would mean that a Layout
 /// object would clip the rows that would overflow the screen height.
+/// [`crate::constraint::solve`] reports this as an error instead.
 ///
 /// # Arguments
 ///
@@ -57,7 +64,7 @@ impl HasEdge for Edge {
 #[allow(clippy::cast_possible_truncation)]
 #[allow(clippy::cast_sign_loss)]
 #[allow(clippy::cast_possible_wrap)]
-fn ratio_resolve<E: HasEdge>(total: u32, edges: &[E]) -> Vec<u32> {
+pub(crate) fn ratio_resolve<E: HasEdge>(total: u32, edges: &[E]) -> Vec<u32> {
     let mut sizes: Vec<Option<u32>> = edges.iter().map(|edge| edge.protocol().size).collect();
 
     // While any edges haven't been calculated.
@@ -142,91 +149,249 @@ fn ratio_resolve<E: HasEdge>(total: u32, edges: &[E]) -> Vec<u32> {
     sizes.into_iter().flatten().collect()
 }
 
-/// Divide a integer total in to parts based on ratios.
+/// An error from [`ratio_reduce`] or [`ratio_distribute`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum RatioError {
+    /// A slice doesn't have one item for each ratio.
+    Length {
+        /// The number of ratios.
+        expected: usize,
+        /// The number of items in the slice.
+        actual: usize,
+    },
+    /// A maximum is larger than the value it would be taken from.
+    Maximum {
+        /// The index of the slot.
+        index: usize,
+        /// The maximum for the slot.
+        maximum: u32,
+        /// The value in the slot.
+        value: u32,
+    },
+    /// The minimums add up to more than the total.
+    Minimums {
+        /// The total to divide.
+        total: u32,
+        /// The sum of the minimums.
+        minimums: u64,
+    },
+}
+
+impl Display for RatioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length { expected, actual } => {
+                write!(
+                    f,
+                    "expected {expected} items to match the ratios, got {actual}"
+                )
+            },
+            Self::Maximum {
+                index,
+                maximum,
+                value,
+            } => write!(
+                f,
+                "maximum of {maximum} is larger than the value of {value} at index {index}"
+            ),
+            Self::Minimums { total, minimums } => {
+                write!(
+                    f,
+                    "minimums add up to {minimums}, which is more than the total of {total}"
+                )
+            },
+        }
+    }
+}
+
+impl Error for RatioError {}
+
+/// Check that a slice has one item for each ratio.
+const fn check_length<T>(ratios: &[u32], items: &[T]) -> Result<(), RatioError> {
+    if ratios.len() == items.len() {
+        Ok(())
+    } else {
+        Err(RatioError::Length {
+            expected: ratios.len(),
+            actual: items.len(),
+        })
+    }
+}
+
+/// Get the whole number part of a non-negative fraction.
+#[allow(clippy::cast_possible_truncation)]
+fn whole(fraction: Fraction) -> u32 {
+    (fraction.numer().copied().unwrap_or(0) / fraction.denom().copied().unwrap_or(1)) as u32
+}
+
+/// Reduce integer values by a total, taken from each value based on ratios.
+///
+/// The amount taken from each value is rounded to the nearest integer (with
+/// halves rounded up), and limited by its maximum. Values with a ratio or
+/// maximum of 0 are left as they are.
+///
+/// The amounts taken add up to exactly `total`, unless the maximums of the
+/// values with a ratio add up to less, in which case every one of those values
+/// is reduced by its maximum.
 ///
 /// # Arguments
 ///
-/// * `total` - The integer total to divide.
-/// * `ratios` - A vector of integer ratios.
-/// * `maximums` - A vector of maximum values for each slot.
-/// * `values` - A vector of values.
+/// * `total` - The total to take from the values.
+/// * `ratios` - The share of the total to take from each value.
+/// * `maximums` - The most to take from each value.
+/// * `values` - The values to reduce.
 ///
 /// # Returns
 ///
-/// A vector of integers guaranteed to sum to total.
-#[allow(clippy::cast_precision_loss)]
+/// A vector of the reduced values.
+///
+/// # Errors
+///
+/// [`RatioError`] if `maximums` or `values` don't have one item for each ratio,
+/// or if a maximum is larger than its value.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::ratio::ratio_reduce;
+///
+/// assert_eq!(ratio_reduce(3, &[2, 4], &[3, 3], &[5, 5]), Ok(vec![4, 3]));
+/// assert_eq!(ratio_reduce(10, &[1, 1], &[2, 2], &[5, 5]), Ok(vec![3, 3]));
+/// ```
 #[allow(clippy::cast_possible_truncation)]
-fn ratio_reduce(total: i32, ratios: &[i32], maximums: &[i32], values: Vec<i32>) -> Vec<i32> {
-    let ratios = zip(ratios, maximums).map(|(ratio, max)| if max == &0 { &0 } else { ratio });
-
-    let mut total_ratio: i32 = ratios.clone().sum();
-    if total_ratio == 0 {
-        return values;
+pub fn ratio_reduce(
+    total: u32,
+    ratios: &[u32],
+    maximums: &[u32],
+    values: &[u32],
+) -> Result<Vec<u32>, RatioError> {
+    check_length(ratios, maximums)?;
+    check_length(ratios, values)?;
+    for (index, (&maximum, &value)) in zip(maximums, values).enumerate() {
+        if maximum > value {
+            return Err(RatioError::Maximum {
+                index,
+                maximum,
+                value,
+            });
+        }
     }
 
-    let mut total_remaining = total;
-    let mut result: Vec<i32> = vec![];
-    for ((ratio, maximum), value) in ratios.zip(maximums).zip(values) {
-        if ratio != &0 && total_ratio > 0 {
-            let distributed = (*maximum as f32)
-                .min((ratio * total_remaining) as f32 / total_ratio as f32)
-                .round() as i32;
-            result.push(value - distributed);
-            total_remaining -= distributed;
-            total_ratio -= ratio;
-        } else {
+    let ratios: Vec<u32> = zip(ratios, maximums)
+        .map(|(&ratio, &maximum)| if maximum == 0 { 0 } else { ratio })
+        .collect();
+
+    let mut total_ratio: u64 = ratios.iter().copied().map(u64::from).sum();
+    // The most that can be taken from the values after each slot.
+    let mut capacity: u64 = zip(&ratios, maximums)
+        .filter(|(&ratio, _)| ratio != 0)
+        .map(|(_, &maximum)| u64::from(maximum))
+        .sum();
+    let mut total_remaining = u64::from(total).min(capacity);
+
+    let mut result = Vec::with_capacity(values.len());
+    for ((&ratio, &maximum), &value) in zip(zip(&ratios, maximums), values) {
+        if ratio == 0 {
             result.push(value);
+            continue;
         }
+
+        capacity -= u64::from(maximum);
+        let share = whole((Fraction::new(u64::from(ratio) * total_remaining, total_ratio)).round());
+        // Take enough that the later values can make up the rest. This is at most
+        // `maximum`, so it fits in a `u32`.
+        let distributed = u64::from(share)
+            .max(total_remaining.saturating_sub(capacity))
+            .min(u64::from(maximum)) as u32;
+
+        result.push(value - distributed);
+        total_remaining -= u64::from(distributed);
+        total_ratio -= u64::from(ratio);
     }
 
-    result
+    Ok(result)
 }
 
 /// Distribute an integer total in to parts based on ratios.
 ///
+/// Each part is its share of the total rounded up, and at least its minimum.
+/// As in Rich, a part with a minimum of 0 is given no share of the total.
+///
+/// The parts add up to exactly `total` (unless there are no ratios).
+///
 /// # Arguments
 ///
 /// * `total` - The total to divide.
-/// * `ratios` - A vector of integer ratios.
-/// * `minimums` - Optional vector of minimum values for each slot.
+/// * `ratios` - The share of the total for each part.
+/// * `minimums` - Optional minimum value for each part.
 ///
 /// # Returns
 ///
-/// A vector of integers guaranteed to sum to total.
-#[allow(clippy::cast_precision_loss)]
+/// A vector of the parts.
+///
+/// # Errors
+///
+/// [`RatioError`] if `minimums` doesn't have one item for each ratio, or if
+/// the minimums add up to more than `total`.
+///
+/// # Examples
+///
+/// ```
+/// use wealthy::ratio::ratio_distribute;
+///
+/// assert_eq!(ratio_distribute(12, &[1, 3], None), Ok(vec![3, 9]));
+/// assert_eq!(ratio_distribute(10, &[1, 1], Some(&[1, 6])), Ok(vec![4, 6]));
+/// ```
 #[allow(clippy::cast_possible_truncation)]
-fn ratio_distribute(total: i32, ratios: Vec<i32>, minimums: Option<Vec<i32>>) -> Vec<i32> {
-    let ratios = if let Some(minimums) = minimums.clone() {
+pub fn ratio_distribute(
+    total: u32,
+    ratios: &[u32],
+    minimums: Option<&[u32]>,
+) -> Result<Vec<u32>, RatioError> {
+    let (ratios, minimums): (Vec<u32>, Vec<u32>) = if let Some(minimums) = minimums {
+        check_length(ratios, minimums)?;
         zip(ratios, minimums)
-            .map(|(ratio, min)| if min == 0 { 0 } else { ratio })
-            .collect()
+            .map(|(&ratio, &minimum)| (if minimum == 0 { 0 } else { ratio }, minimum))
+            .unzip()
     } else {
-        ratios
+        (ratios.to_vec(), vec![0; ratios.len()])
     };
 
-    let mut total_ratio: i32 = ratios.iter().sum();
-    let mut total_remaining = total;
-    let mut distributed_total: Vec<i32> = vec![];
+    let minimums_total: u64 = minimums.iter().copied().map(u64::from).sum();
+    if minimums_total > u64::from(total) {
+        return Err(RatioError::Minimums {
+            total,
+            minimums: minimums_total,
+        });
+    }
 
-    let minimums = minimums.unwrap_or_else(|| vec![0; ratios.len()]);
+    let mut total_ratio: u64 = ratios.iter().copied().map(u64::from).sum();
+    let mut total_remaining = total;
+    // The least that must be left for the later parts, which is at most `total`.
+    let mut reserved = minimums_total as u32;
 
+    let mut distributed_total = Vec::with_capacity(ratios.len());
     for (ratio, minimum) in zip(ratios, minimums) {
+        reserved -= minimum;
         let distributed = if total_ratio > 0 {
-            (minimum as f32).max(((ratio * total_remaining) as f32 / total_ratio as f32).ceil())
-                as i32
+            whole(Fraction::new(u64::from(ratio) * u64::from(total_remaining), total_ratio).ceil())
+                .max(minimum)
         } else {
             total_remaining
         };
+        // Leave enough for the later parts to reach their minimums.
+        let distributed = distributed.min(total_remaining - reserved);
 
         distributed_total.push(distributed);
-        total_ratio -= ratio;
+        total_ratio -= u64::from(ratio);
         total_remaining -= distributed;
     }
 
-    distributed_total
+    Ok(distributed_total)
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use rstest::rstest;
 
@@ -253,36 +418,97 @@ mod tests {
     }
 
     #[rstest]
-    #[case(20, &vec![2, 4], &vec![20, 20], vec![5, 5], &vec![-2, -8])]
-    #[case(20, &vec![2, 4], &vec![1, 1], vec![5, 5], &vec![4, 4])]
-    #[case(20, &vec![2, 4], &vec![1, 1], vec![2, 2], &vec![1, 1])]
-    #[case(3, &vec![2, 4], &vec![3, 3], vec![2, 2], &vec![1, 0])]
-    #[case(3, &vec![2, 4], &vec![3, 3], vec![0, 0], &vec![-1, -2])]
-    #[case(3, &vec![0, 0], &vec![3, 3], vec![4, 4], &vec![4, 4])]
-    #[case(3, &vec![5, -6], &vec![3, 3], vec![5, 5], &vec![5, 5])]
+    #[case(20, &[2, 4], &[1, 1], &[5, 5], &[4, 4])]
+    #[case(20, &[2, 4], &[1, 1], &[2, 2], &[1, 1])]
+    #[case(3, &[2, 4], &[2, 2], &[2, 2], &[1, 0])]
+    #[case(3, &[2, 4], &[3, 3], &[5, 5], &[4, 3])]
+    #[case(3, &[0, 0], &[3, 3], &[4, 4], &[4, 4])]
+    #[case(3, &[1, 1], &[0, 3], &[4, 4], &[4, 1])]
+    #[case(10, &[1, 1], &[10, 1], &[10, 10], &[1, 9])]
+    #[case(0, &[], &[], &[], &[])]
+    #[case(1, &[1, 1], &[u32::MAX, u32::MAX], &[u32::MAX, u32::MAX], &[u32::MAX - 1, u32::MAX])]
+    #[case(u32::MAX, &[1, 1], &[u32::MAX, u32::MAX], &[u32::MAX, u32::MAX], &[2_147_483_647, 2_147_483_648])]
     fn test_ratio_reduce(
-        #[case] total: i32,
-        #[case] ratios: &[i32],
-        #[case] maximums: &[i32],
-        #[case] values: Vec<i32>,
-        #[case] result: &[i32],
+        #[case] total: u32,
+        #[case] ratios: &[u32],
+        #[case] maximums: &[u32],
+        #[case] values: &[u32],
+        #[case] result: &[u32],
+    ) {
+        assert_eq!(
+            ratio_reduce(total, ratios, maximums, values).unwrap(),
+            result
+        );
+    }
+
+    #[rstest]
+    #[case(20, &[2, 4], &[20, 20], &[5, 5], RatioError::Maximum { index: 0, maximum: 20, value: 5 })]
+    #[case(3, &[2, 4], &[3], &[5, 5], RatioError::Length { expected: 2, actual: 1 })]
+    #[case(3, &[2, 4], &[3, 3], &[5, 5, 5], RatioError::Length { expected: 2, actual: 3 })]
+    fn test_ratio_reduce_error(
+        #[case] total: u32,
+        #[case] ratios: &[u32],
+        #[case] maximums: &[u32],
+        #[case] values: &[u32],
+        #[case] error: RatioError,
     ) {
-        assert_eq!(ratio_reduce(total, ratios, maximums, values), result);
+        assert_eq!(ratio_reduce(total, ratios, maximums, values), Err(error));
     }
 
     #[rstest]
-    #[case(10, vec![1], None, &vec![10])]
-    #[case(10, vec![1, 1], None, &vec![5, 5])]
-    #[case(12, vec![1, 3], None, &vec![3, 9])]
-    #[case(0, vec![1, 3], None, &vec![0, 0])]
-    #[case(0, vec![1, 3], Some(vec![1, 1]), &vec![1, 1])]
-    #[case(10, vec![1, 0], None, &vec![10, 0])]
+    #[case(10, &[1], None, &[10])]
+    #[case(10, &[1, 1], None, &[5, 5])]
+    #[case(12, &[1, 3], None, &[3, 9])]
+    #[case(0, &[1, 3], None, &[0, 0])]
+    #[case(2, &[1, 3], Some(&[1, 1][..]), &[1, 1])]
+    #[case(10, &[1, 0], None, &[10, 0])]
+    #[case(10, &[1, 1], Some(&[1, 6][..]), &[4, 6])]
+    #[case(10, &[0, 0], Some(&[2, 3][..]), &[7, 3])]
+    #[case(10, &[1, 1], Some(&[0, 2][..]), &[0, 10])]
+    #[case(10, &[], None, &[])]
     fn test_ratio_distribute(
-        #[case] total: i32,
-        #[case] ratios: Vec<i32>,
-        #[case] minimums: Option<Vec<i32>>,
-        #[case] result: &[i32],
+        #[case] total: u32,
+        #[case] ratios: &[u32],
+        #[case] minimums: Option<&[u32]>,
+        #[case] result: &[u32],
     ) {
-        assert_eq!(ratio_distribute(total, ratios, minimums), result);
+        assert_eq!(ratio_distribute(total, ratios, minimums).unwrap(), result);
+    }
+
+    #[rstest]
+    #[case(0, &[1, 3], Some(&[1, 1][..]), RatioError::Minimums { total: 0, minimums: 2 })]
+    #[case(10, &[1, 3], Some(&[1][..]), RatioError::Length { expected: 2, actual: 1 })]
+    fn test_ratio_distribute_error(
+        #[case] total: u32,
+        #[case] ratios: &[u32],
+        #[case] minimums: Option<&[u32]>,
+        #[case] error: RatioError,
+    ) {
+        assert_eq!(ratio_distribute(total, ratios, minimums), Err(error));
+    }
+
+    #[test]
+    fn test_sums_to_total() {
+        for total in 0..40 {
+            for ratios in [&[1, 2, 3][..], &[5, 0, 1], &[7, 7], &[0, 0, 0]] {
+                let minimums = vec![1; ratios.len()];
+                if let Ok(parts) = ratio_distribute(total, ratios, Some(&minimums)) {
+                    assert_eq!(parts.iter().sum::<u32>(), total);
+                } else {
+                    assert!(total < minimums.iter().sum());
+                }
+
+                let values = vec![20; ratios.len()];
+                let reduced = ratio_reduce(total, ratios, &values, &values).unwrap();
+                let capacity: u32 = zip(ratios, &values)
+                    .filter(|(&ratio, _)| ratio != 0)
+                    .map(|(_, &value)| value)
+                    .sum();
+                assert_eq!(
+                    values.iter().sum::<u32>() - reduced.iter().sum::<u32>(),
+                    total.min(capacity)
+                );
+            }
+        }
     }
 }
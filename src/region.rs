@@ -1,9 +1,413 @@
-#![allow(dead_code)]
-
-/// Defines a rectangualar region of the screen.
-pub(crate) struct Region {
-    pub(crate) x: u32,
-    pub(crate) y: u32,
-    pub(crate) width: u32,
-    pub(crate) height: u32,
+use crate::ratio::{ratio_resolve, Edge};
+
+/// Defines a rectangular region of the screen.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Region {
+    /// Offset of the left edge, in cells.
+    pub x: u32,
+    /// Offset of the top edge, in lines.
+    pub y: u32,
+    /// Width in cells.
+    pub width: u32,
+    /// Height in lines.
+    pub height: u32,
+}
+
+impl Region {
+    /// Instantiate a new [`Region`].
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Offset of the left edge, in cells.
+    /// * `y` - Offset of the top edge, in lines.
+    /// * `width` - Width in cells.
+    /// * `height` - Height in lines.
+    pub const fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Get the offset just past the right edge, or [`u32::MAX`] if that is
+    /// out of range.
+    pub const fn right(&self) -> u32 { self.x.saturating_add(self.width) }
+
+    /// Get the offset just past the bottom edge, or [`u32::MAX`] if that is
+    /// out of range.
+    pub const fn bottom(&self) -> u32 { self.y.saturating_add(self.height) }
+
+    /// Check if the region covers no cells.
+    pub const fn is_empty(&self) -> bool { self.width == 0 || self.height == 0 }
+
+    /// Check if a cell is within the region.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Offset of the cell from the left.
+    /// * `y` - Offset of the cell from the top.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::region::Region;
+    ///
+    /// let region = Region::new(10, 10, 5, 5);
+    ///
+    /// assert!(region.contains(10, 14));
+    /// assert!(!region.contains(15, 10));
+    /// ```
+    pub const fn contains(&self, x: u32, y: u32) -> bool {
+        self.x <= x && x < self.right() && self.y <= y && y < self.bottom()
+    }
+
+    /// Check if another region is entirely within this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other region.
+    pub const fn contains_region(&self, other: &Self) -> bool {
+        self.x <= other.x
+            && other.right() <= self.right()
+            && self.y <= other.y
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Get the overlap of two regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other region.
+    ///
+    /// # Returns
+    ///
+    /// The overlapping region, or `None` if the regions don't overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::region::Region;
+    ///
+    /// let region = Region::new(0, 0, 10, 10);
+    ///
+    /// assert_eq!(
+    ///     region.intersection(&Region::new(5, 8, 10, 10)),
+    ///     Some(Region::new(5, 8, 5, 2))
+    /// );
+    /// assert_eq!(region.intersection(&Region::new(10, 0, 5, 5)), None);
+    /// ```
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (x, y) = (self.x.max(other.x), self.y.max(other.y));
+        let (right, bottom) = (
+            self.right().min(other.right()),
+            self.bottom().min(other.bottom()),
+        );
+
+        (x < right && y < bottom).then(|| Self::new(x, y, right - x, bottom - y))
+    }
+
+    /// Get the smallest region that contains both regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other region.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let (x, y) = (self.x.min(other.x), self.y.min(other.y));
+        let (right, bottom) = (
+            self.right().max(other.right()),
+            self.bottom().max(other.bottom()),
+        );
+
+        Self::new(x, y, right - x, bottom - y)
+    }
+
+    /// Move the region.
+    ///
+    /// The region stops at the top and left of the screen rather than moving
+    /// past them.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Cells to move right, or left if negative.
+    /// * `y` - Lines to move down, or up if negative.
+    #[must_use]
+    pub const fn translate(&self, x: i32, y: i32) -> Self {
+        Self::new(
+            self.x.saturating_add_signed(x),
+            self.y.saturating_add_signed(y),
+            self.width,
+            self.height,
+        )
+    }
+
+    /// Remove padding from the edges of the region.
+    ///
+    /// # Arguments
+    ///
+    /// * `top` - Lines to remove from the top.
+    /// * `right` - Cells to remove from the right.
+    /// * `bottom` - Lines to remove from the bottom.
+    /// * `left` - Cells to remove from the left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::region::Region;
+    ///
+    /// assert_eq!(
+    ///     Region::new(0, 0, 10, 10).shrink(1, 2, 3, 4),
+    ///     Region::new(4, 1, 4, 6)
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn shrink(&self, top: u32, right: u32, bottom: u32, left: u32) -> Self {
+        let left = if left < self.width { left } else { self.width };
+        let top = if top < self.height { top } else { self.height };
+
+        Self::new(
+            self.x.saturating_add(left),
+            self.y.saturating_add(top),
+            (self.width - left).saturating_sub(right),
+            (self.height - top).saturating_sub(bottom),
+        )
+    }
+
+    /// Add padding around the edges of the region.
+    ///
+    /// The region stops at the top and left of the screen, and at [`u32::MAX`]
+    /// on the right and bottom, rather than growing past them.
+    ///
+    /// # Arguments
+    ///
+    /// * `top` - Lines to add to the top.
+    /// * `right` - Cells to add to the right.
+    /// * `bottom` - Lines to add to the bottom.
+    /// * `left` - Cells to add to the left.
+    #[must_use]
+    pub const fn grow(&self, top: u32, right: u32, bottom: u32, left: u32) -> Self {
+        let (x, y) = (self.x.saturating_sub(left), self.y.saturating_sub(top));
+
+        Self::new(
+            x,
+            y,
+            self.right().saturating_add(right) - x,
+            self.bottom().saturating_add(bottom) - y,
+        )
+    }
+
+    /// Split the region in to columns (side by side) with widths based on
+    /// ratios.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratios` - The share of the width given to each column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::region::Region;
+    ///
+    /// assert_eq!(
+    ///     Region::new(0, 0, 10, 4).split_vertical(&[1, 4]),
+    ///     [Region::new(0, 0, 2, 4), Region::new(2, 0, 8, 4)]
+    /// );
+    /// ```
+    pub fn split_vertical(&self, ratios: &[u32]) -> Vec<Self> {
+        let mut x = self.x;
+
+        split_sizes(self.width, ratios)
+            .into_iter()
+            .map(|width| {
+                let region = Self::new(x, self.y, width, self.height);
+                x = x.saturating_add(width);
+                region
+            })
+            .collect()
+    }
+
+    /// Split the region in to rows (stacked on top of each other) with heights
+    /// based on ratios.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratios` - The share of the height given to each row.
+    pub fn split_horizontal(&self, ratios: &[u32]) -> Vec<Self> {
+        let mut y = self.y;
+
+        split_sizes(self.height, ratios)
+            .into_iter()
+            .map(|height| {
+                let region = Self::new(self.x, y, self.width, height);
+                y = y.saturating_add(height);
+                region
+            })
+            .collect()
+    }
+}
+
+/// Divide `total` in to sizes based on ratios, with the sizes adding up to
+/// `total` (unless all the ratios are 0).
+fn split_sizes(total: u32, ratios: &[u32]) -> Vec<u32> {
+    if total == 0 {
+        return vec![0; ratios.len()];
+    }
+
+    let edges: Vec<_> = ratios
+        .iter()
+        .map(|&ratio| Edge {
+            size: (ratio == 0).then_some(0),
+            ratio,
+            minimum_size: 0,
+        })
+        .collect();
+
+    ratio_resolve(total, &edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, 0, true)]
+    #[case(9, 4, true)]
+    #[case(10, 0, false)]
+    #[case(0, 5, false)]
+    fn test_contains(#[case] x: u32, #[case] y: u32, #[case] result: bool) {
+        assert_eq!(Region::new(0, 0, 10, 5).contains(x, y), result);
+        assert!(!Region::new(0, 0, 0, 0).contains(0, 0));
+    }
+
+    #[rstest]
+    #[case(Region::new(2, 2, 2, 2), true)]
+    #[case(Region::new(0, 0, 10, 5), true)]
+    #[case(Region::new(5, 0, 6, 5), false)]
+    fn test_contains_region(#[case] other: Region, #[case] result: bool) {
+        assert_eq!(Region::new(0, 0, 10, 5).contains_region(&other), result);
+    }
+
+    #[rstest]
+    #[case(Region::new(5, 5, 10, 10), Some(Region::new(5, 5, 5, 5)))]
+    #[case(Region::new(2, 2, 2, 2), Some(Region::new(2, 2, 2, 2)))]
+    #[case(Region::new(10, 0, 5, 5), None)]
+    #[case(Region::new(0, 10, 5, 5), None)]
+    #[case(Region::new(3, 3, 0, 0), None)]
+    fn test_intersection(#[case] other: Region, #[case] result: Option<Region>) {
+        let region = Region::new(0, 0, 10, 10);
+
+        assert_eq!(region.intersection(&other), result);
+        assert_eq!(other.intersection(&region), result);
+    }
+
+    #[rstest]
+    #[case(Region::new(5, 5, 10, 10), Region::new(0, 0, 15, 15))]
+    #[case(Region::new(2, 2, 2, 2), Region::new(0, 0, 10, 10))]
+    #[case(Region::new(20, 1, 1, 1), Region::new(0, 0, 21, 10))]
+    fn test_union(#[case] other: Region, #[case] result: Region) {
+        let region = Region::new(0, 0, 10, 10);
+
+        assert_eq!(region.union(&other), result);
+        assert_eq!(other.union(&region), result);
+    }
+
+    #[test]
+    fn test_edges_saturate() {
+        let region = Region::new(u32::MAX - 11, u32::MAX - 11, 4, 4).translate(10, 10);
+
+        assert_eq!(region, Region::new(u32::MAX - 1, u32::MAX - 1, 4, 4));
+        assert_eq!((region.right(), region.bottom()), (u32::MAX, u32::MAX));
+        assert_eq!(
+            region.grow(1, 1, 1, 1),
+            Region::new(u32::MAX - 2, u32::MAX - 2, 2, 2)
+        );
+        assert_eq!(
+            region.shrink(1, 0, 0, 1),
+            Region::new(u32::MAX, u32::MAX, 3, 3)
+        );
+        assert!(region.contains(u32::MAX - 1, u32::MAX - 1));
+        assert_eq!(
+            region.split_vertical(&[1, 1]),
+            [
+                Region::new(u32::MAX - 1, u32::MAX - 1, 2, 4),
+                Region::new(u32::MAX, u32::MAX - 1, 2, 4)
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(3, -2, Region::new(8, 3, 4, 4))]
+    #[case(-10, -10, Region::new(0, 0, 4, 4))]
+    fn test_translate(#[case] x: i32, #[case] y: i32, #[case] result: Region) {
+        assert_eq!(Region::new(5, 5, 4, 4).translate(x, y), result);
+    }
+
+    #[rstest]
+    #[case((1, 1, 1, 1), Region::new(6, 6, 8, 8))]
+    #[case((0, 20, 0, 0), Region::new(5, 5, 0, 10))]
+    #[case((20, 0, 0, 20), Region::new(15, 15, 0, 0))]
+    fn test_shrink(#[case] padding: (u32, u32, u32, u32), #[case] result: Region) {
+        let (top, right, bottom, left) = padding;
+
+        assert_eq!(
+            Region::new(5, 5, 10, 10).shrink(top, right, bottom, left),
+            result
+        );
+    }
+
+    #[rstest]
+    #[case((1, 2, 3, 4), Region::new(1, 4, 16, 14))]
+    #[case((10, 0, 0, 10), Region::new(0, 0, 15, 15))]
+    #[case((0, u32::MAX, u32::MAX, 0), Region::new(5, 5, u32::MAX - 5, u32::MAX - 5))]
+    fn test_grow(#[case] padding: (u32, u32, u32, u32), #[case] result: Region) {
+        let (top, right, bottom, left) = padding;
+
+        assert_eq!(
+            Region::new(5, 5, 10, 10).grow(top, right, bottom, left),
+            result
+        );
+    }
+
+    #[rstest]
+    #[case(&[1, 1], &[(10, 5), (15, 5)])]
+    #[case(&[1, 1, 1], &[(10, 3), (13, 3), (16, 4)])]
+    #[case(&[1, 0, 3], &[(10, 2), (12, 0), (12, 8)])]
+    #[case(&[], &[])]
+    fn test_split_vertical(#[case] ratios: &[u32], #[case] result: &[(u32, u32)]) {
+        let regions: Vec<_> = Region::new(10, 20, 10, 4)
+            .split_vertical(ratios)
+            .into_iter()
+            .map(|region| {
+                assert_eq!((region.y, region.height), (20, 4));
+                (region.x, region.width)
+            })
+            .collect();
+
+        assert_eq!(regions, result);
+    }
+
+    #[rstest]
+    #[case(&[1, 2], &[(20, 2), (22, 4)])]
+    #[case(&[1, 1], &[(20, 3), (23, 3)])]
+    fn test_split_horizontal(#[case] ratios: &[u32], #[case] result: &[(u32, u32)]) {
+        let regions: Vec<_> = Region::new(10, 20, 10, 6)
+            .split_horizontal(ratios)
+            .into_iter()
+            .map(|region| {
+                assert_eq!((region.x, region.width), (10, 10));
+                (region.y, region.height)
+            })
+            .collect();
+
+        assert_eq!(regions, result);
+        assert_eq!(
+            Region::new(0, 0, 4, 0).split_horizontal(&[1, 1]),
+            [Region::new(0, 0, 4, 0); 2]
+        );
+    }
 }
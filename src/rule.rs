@@ -0,0 +1,123 @@
+use crate::cells::{cell_len, set_cell_size};
+use crate::console::{Console, ConsoleOptions, Renderable};
+use crate::measure::Measurement;
+use crate::segment::Segment;
+use crate::style::Style;
+
+/// A console renderable to draw a horizontal rule (line).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rule {
+    /// Text to render in the rule.
+    pub title: String,
+    /// Character(s) used to draw the line.
+    pub characters: String,
+    /// Style of the rule line.
+    pub style: Style,
+}
+
+impl Rule {
+    /// Instantiate a new [`Rule`].
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - Text to render in the rule, or an empty string for no title.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            characters: "─".into(),
+            style: Style::parse("bright_green").unwrap(),
+        }
+    }
+
+    /// Get `width` cells worth of the rule characters.
+    fn line(&self, width: u32) -> String {
+        let characters_width = cell_len(&self.characters).max(1);
+
+        set_cell_size(
+            self.characters
+                .repeat((width / characters_width + 1) as usize),
+            width,
+        )
+    }
+}
+
+impl Renderable for Rule {
+    fn render(&self, _console: &Console<'_>, options: &ConsoleOptions) -> Vec<Segment> {
+        let width = options.max_width;
+        let title = self.title.replace('\n', " ");
+        let truncate_width = width.saturating_sub(4);
+
+        if title.is_empty() || truncate_width == 0 {
+            return vec![
+                Segment::new(self.line(width), Some(self.style.clone())),
+                Segment::line(),
+            ];
+        }
+
+        let title = if cell_len(&title) > truncate_width {
+            set_cell_size(title, truncate_width - 1) + "…"
+        } else {
+            title
+        };
+        let side_width = (width - cell_len(&title)) / 2;
+        let left = self.line(side_width - 1);
+        let right = self.line(width - cell_len(&left) - cell_len(&title) - 2);
+
+        vec![
+            Segment::new(left + " ", Some(self.style.clone())),
+            Segment::new(title, None),
+            Segment::new(" ".to_owned() + &right, Some(self.style.clone())),
+            Segment::line(),
+        ]
+    }
+
+    fn measure(&self, _console: &Console<'_>, _options: &ConsoleOptions) -> Option<Measurement> {
+        Some(Measurement::new(1, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", 10, "──────────")]
+    #[case("foo", 11, "─── foo ───")]
+    #[case("foo", 12, "─── foo ────")]
+    #[case("foobarbaz", 10, "─ fooba… ─")]
+    #[case("foo", 4, "────")]
+    fn test_rule(#[case] title: &str, #[case] width: u32, #[case] result: &str) {
+        let console = Console::new().with_width(width);
+        let segments = console.render(&Rule::new(title), None);
+        let text: String = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect();
+
+        assert_eq!(text, format!("{result}\n"));
+    }
+
+    #[test]
+    fn test_measure() {
+        let console = Console::new().with_width(20);
+
+        assert_eq!(
+            console.measure(&Rule::new("foo"), None),
+            Measurement::new(1, 1)
+        );
+    }
+
+    #[test]
+    fn test_characters() {
+        let rule = Rule {
+            characters: "=-".into(),
+            ..Rule::new("")
+        };
+        let console = Console::new().with_width(5);
+
+        assert_eq!(console.render(&rule, None)[0].text, "=-=-=");
+    }
+}
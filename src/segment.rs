@@ -0,0 +1,904 @@
+use std::fmt::{self, Display};
+
+use crate::cells::{cell_len, get_character_cell_size, set_cell_size};
+use crate::style::Style;
+
+/// Non-printable control codes which typically translate to ANSI codes.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ControlCode {
+    /// Ring the terminal bell (`BEL`).
+    Bell,
+    /// Move the cursor to the start of the line (`CR`).
+    CarriageReturn,
+    /// Move the cursor to the top left of the screen (`CSI H`).
+    Home,
+    /// Clear the screen (`CSI 2 J`).
+    Clear,
+    /// Show the cursor (`CSI ? 25 h`).
+    ShowCursor,
+    /// Hide the cursor (`CSI ? 25 l`).
+    HideCursor,
+    /// Switch to the alternate screen (`CSI ? 1049 h`).
+    EnableAltScreen,
+    /// Switch back from the alternate screen (`CSI ? 1049 l`).
+    DisableAltScreen,
+    /// Move the cursor up a number of lines (`CSI n A`).
+    CursorUp(u32),
+    /// Move the cursor down a number of lines (`CSI n B`).
+    CursorDown(u32),
+    /// Move the cursor right a number of cells (`CSI n C`).
+    CursorForward(u32),
+    /// Move the cursor left a number of cells (`CSI n D`).
+    CursorBackward(u32),
+    /// Move the cursor to a column, counting from 0 (`CSI n G`, with `n` one
+    /// higher).
+    CursorMoveToColumn(u32),
+    /// Move the cursor to a column and line, counting from 0 (`CSI y ; x H`,
+    /// with both one higher).
+    CursorMoveTo(u32, u32),
+    /// Erase part of the line (`CSI n K`): to the end of the line for 0, to the
+    /// start for 1, or the whole line for 2.
+    EraseInLine(u32),
+    /// Set the title of the terminal window (`OSC 0 ; title BEL`).
+    SetWindowTitle(String),
+}
+
+impl Display for ControlCode {
+    /// Write the ANSI escape sequence for the control code.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bell => write!(f, "\x07"),
+            Self::CarriageReturn => write!(f, "\r"),
+            Self::Home => write!(f, "\x1b[H"),
+            Self::Clear => write!(f, "\x1b[2J"),
+            Self::ShowCursor => write!(f, "\x1b[?25h"),
+            Self::HideCursor => write!(f, "\x1b[?25l"),
+            Self::EnableAltScreen => write!(f, "\x1b[?1049h"),
+            Self::DisableAltScreen => write!(f, "\x1b[?1049l"),
+            Self::CursorUp(count) => write!(f, "\x1b[{count}A"),
+            Self::CursorDown(count) => write!(f, "\x1b[{count}B"),
+            Self::CursorForward(count) => write!(f, "\x1b[{count}C"),
+            Self::CursorBackward(count) => write!(f, "\x1b[{count}D"),
+            Self::CursorMoveToColumn(column) => write!(f, "\x1b[{}G", column + 1),
+            Self::CursorMoveTo(x, y) => write!(f, "\x1b[{};{}H", y + 1, x + 1),
+            Self::EraseInLine(mode) => write!(f, "\x1b[{mode}K"),
+            Self::SetWindowTitle(title) => write!(f, "\x1b]0;{title}\x07"),
+        }
+    }
+}
+
+/// A piece of text with associated style. Segments are produced by the
+/// `Console` render process and are ultimately converted in to strings to be
+/// written to the terminal.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Segment {
+    /// A piece of text.
+    pub text: String,
+    /// An optional style to apply to the text.
+    pub style: Option<Style>,
+    /// Optional sequence of control codes.
+    pub control: Option<Vec<ControlCode>>,
+}
+
+impl Segment {
+    /// Instantiate a new [`Segment`].
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A piece of text.
+    /// * `style` - An optional style to apply to the text.
+    pub fn new(text: impl Into<String>, style: Option<Style>) -> Self {
+        Self {
+            text: text.into(),
+            style,
+            control: None,
+        }
+    }
+
+    /// Instantiate a new control [`Segment`].
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - A sequence of control codes.
+    pub fn new_control(control: Vec<ControlCode>) -> Self {
+        Self {
+            text: String::new(),
+            style: None,
+            control: Some(control),
+        }
+    }
+
+    /// Make a new line segment.
+    pub fn line() -> Self { Self::new("\n", None) }
+
+    /// Get the number of cells required to render this segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::segment::Segment;
+    ///
+    /// assert_eq!(Segment::new("foo", None).cell_length(), 3);
+    /// assert_eq!(Segment::new("💩", None).cell_length(), 2);
+    /// ```
+    pub fn cell_length(&self) -> u32 {
+        if self.is_control() {
+            0
+        } else {
+            cell_len(&self.text)
+        }
+    }
+
+    /// Check if the segment contains control codes.
+    pub const fn is_control(&self) -> bool { self.control.is_some() }
+
+    /// Split the segment in to two at a given cell position.
+    ///
+    /// If the cut point falls in the middle of a 2-cell wide character then it
+    /// is replaced by two spaces, to preserve the display width of the parent
+    /// segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `cut` - Offset within the segment to cut.
+    ///
+    /// # Returns
+    ///
+    /// The segments before and after the cut.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::segment::Segment;
+    ///
+    /// let (before, after) = Segment::new("X💩Y", None).split_cells(2);
+    ///
+    /// assert_eq!(before.text, "X ");
+    /// assert_eq!(after.text, " Y");
+    /// ```
+    pub fn split_cells(&self, cut: u32) -> (Self, Self) {
+        let with_text = |text: String| Self {
+            text,
+            style: self.style.clone(),
+            control: self.control.clone(),
+        };
+
+        let mut cell_position = 0;
+        for (index, character) in self.text.char_indices() {
+            if cell_position == cut {
+                return (
+                    with_text(self.text[..index].into()),
+                    with_text(self.text[index..].into()),
+                );
+            }
+
+            cell_position += get_character_cell_size(character);
+
+            if cell_position > cut {
+                let end = index + character.len_utf8();
+
+                return (
+                    with_text(format!("{} ", &self.text[..index])),
+                    with_text(format!(" {}", &self.text[end..])),
+                );
+            }
+        }
+
+        (self.clone(), with_text(String::new()))
+    }
+
+    /// Apply style(s) to an iterable of segments.
+    ///
+    /// Returns an iterable of segments where the style is replaced by
+    /// `style + segment.style + post_style`.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - Segments to process.
+    /// * `style` - Base style.
+    /// * `post_style` - Style to apply on top of segment style.
+    pub fn apply_style(
+        segments: &[Self],
+        style: Option<&Style>,
+        post_style: Option<&Style>,
+    ) -> Vec<Self> {
+        segments
+            .iter()
+            .map(|segment| {
+                if segment.is_control() {
+                    return segment.clone();
+                }
+
+                let combined = [style, segment.style.as_ref(), post_style]
+                    .into_iter()
+                    .flatten()
+                    .fold(None, |combined: Option<Style>, style| {
+                        Some(combined.map_or_else(|| style.clone(), |combined| &combined + style))
+                    });
+
+                Self::new(segment.text.clone(), combined)
+            })
+            .collect()
+    }
+
+    /// Filter segments by their `is_control` status.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - Segments to filter.
+    /// * `is_control` - `true` to keep only control segments, `false` to keep
+    ///   only non-control segments.
+    pub fn filter_control(segments: &[Self], is_control: bool) -> Vec<Self> {
+        segments
+            .iter()
+            .filter(|segment| segment.is_control() == is_control)
+            .cloned()
+            .collect()
+    }
+
+    /// Split a sequence of segments in to a list of lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - Segments potentially containing line feeds.
+    ///
+    /// # Returns
+    ///
+    /// A vector of lines, each a vector of segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::segment::Segment;
+    ///
+    /// assert_eq!(
+    ///     Segment::split_lines(&[Segment::new("Hello\nWorld", None)]),
+    ///     vec![
+    ///         vec![Segment::new("Hello", None)],
+    ///         vec![Segment::new("World", None)],
+    ///     ]
+    /// );
+    /// ```
+    pub fn split_lines(segments: &[Self]) -> Vec<Vec<Self>> {
+        let mut lines = vec![];
+        let mut line = vec![];
+
+        for segment in segments {
+            if segment.text.contains('\n') && !segment.is_control() {
+                let mut parts = segment.text.split('\n').peekable();
+
+                while let Some(text) = parts.next() {
+                    if !text.is_empty() {
+                        line.push(Self::new(text, segment.style.clone()));
+                    }
+                    if parts.peek().is_some() {
+                        lines.push(std::mem::take(&mut line));
+                    }
+                }
+            } else {
+                line.push(segment.clone());
+            }
+        }
+
+        if !line.is_empty() {
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /// Split segments in to lines, and crop lines greater than a given length.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - Segments to split.
+    /// * `length` - Desired line length.
+    /// * `style` - Style to use for any padding.
+    /// * `pad` - Enable padding of lines that are less than `length`.
+    /// * `include_new_lines` - Include a new line segment at the end of each
+    ///   line.
+    ///
+    /// # Returns
+    ///
+    /// A vector of lines, each a vector of segments.
+    pub fn split_and_crop_lines(
+        segments: &[Self],
+        length: u32,
+        style: Option<&Style>,
+        pad: bool,
+        include_new_lines: bool,
+    ) -> Vec<Vec<Self>> {
+        let mut lines = vec![];
+        let mut line = vec![];
+
+        for segment in segments {
+            if segment.text.contains('\n') && !segment.is_control() {
+                let mut parts = segment.text.split('\n').peekable();
+
+                while let Some(text) = parts.next() {
+                    if !text.is_empty() {
+                        line.push(Self::new(text, segment.style.clone()));
+                    }
+                    if parts.peek().is_some() {
+                        let mut cropped_line = Self::adjust_line_length(&line, length, style, pad);
+                        if include_new_lines {
+                            cropped_line.push(Self::line());
+                        }
+                        lines.push(cropped_line);
+                        line.clear();
+                    }
+                }
+            } else {
+                line.push(segment.clone());
+            }
+        }
+
+        if !line.is_empty() {
+            lines.push(Self::adjust_line_length(&line, length, style, pad));
+        }
+
+        lines
+    }
+
+    /// Adjust a line to a given width (cropping or padding as required).
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - A line of segments.
+    /// * `length` - The desired width of the line.
+    /// * `style` - The style of padding if used (space on the end).
+    /// * `pad` - Pad lines with spaces if they are shorter than `length`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::segment::Segment;
+    ///
+    /// let line = [Segment::new("H", None), Segment::new("ello, World!", None)];
+    ///
+    /// assert_eq!(
+    ///     Segment::adjust_line_length(&line, 5, None, true),
+    ///     [Segment::new("H", None), Segment::new("ello", None)]
+    /// );
+    /// ```
+    pub fn adjust_line_length(
+        line: &[Self],
+        length: u32,
+        style: Option<&Style>,
+        pad: bool,
+    ) -> Vec<Self> {
+        let line_length = Self::get_line_length(line);
+
+        if line_length < length {
+            let mut new_line = line.to_vec();
+            if pad {
+                new_line.push(Self::new(
+                    " ".repeat((length - line_length) as usize),
+                    style.cloned(),
+                ));
+            }
+
+            return new_line;
+        }
+
+        if line_length == length {
+            return line.to_vec();
+        }
+
+        let mut new_line = vec![];
+        let mut line_length = 0;
+        for segment in line {
+            let segment_length = segment.cell_length();
+
+            if line_length + segment_length < length || segment.is_control() {
+                new_line.push(segment.clone());
+                line_length += segment_length;
+            } else {
+                new_line.push(Self::new(
+                    set_cell_size(segment.text.clone(), length - line_length),
+                    segment.style.clone(),
+                ));
+                break;
+            }
+        }
+
+        new_line
+    }
+
+    /// Get the length of a line of segments, in cells.
+    pub fn get_line_length(line: &[Self]) -> u32 { line.iter().map(Self::cell_length).sum() }
+
+    /// Get the shape (enclosing rectangle) of a list of lines.
+    ///
+    /// # Returns
+    ///
+    /// Width and height in characters.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn get_shape(lines: &[Vec<Self>]) -> (u32, u32) {
+        let max_width = lines
+            .iter()
+            .map(|line| Self::get_line_length(line))
+            .max()
+            .unwrap_or(0);
+
+        (max_width, lines.len() as u32)
+    }
+
+    /// Set the shape of a list of lines (enclosing rectangle).
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - A list of lines.
+    /// * `width` - Desired width.
+    /// * `height` - Desired height or `None` for no change.
+    /// * `style` - Style of any padding added.
+    /// * `new_lines` - Padded lines should include `\n`.
+    pub fn set_shape(
+        lines: &[Vec<Self>],
+        width: u32,
+        height: Option<u32>,
+        style: Option<&Style>,
+        new_lines: bool,
+    ) -> Vec<Vec<Self>> {
+        let height = height.map_or(lines.len(), |height| height as usize);
+
+        let mut blank = vec![Self::new(" ".repeat(width as usize), style.cloned())];
+        if new_lines {
+            blank.push(Self::line());
+        }
+
+        let mut shaped_lines: Vec<Vec<Self>> = lines
+            .iter()
+            .take(height)
+            .map(|line| Self::adjust_line_length(line, width, style, true))
+            .collect();
+        shaped_lines.resize(height, blank);
+
+        shaped_lines
+    }
+
+    /// Simplify an iterable of segments by combining contiguous segments with
+    /// the same style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::segment::Segment;
+    ///
+    /// let segments = [
+    ///     Segment::new("Hello", None),
+    ///     Segment::new(" ", None),
+    ///     Segment::new("World!", None),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     Segment::simplify(&segments),
+    ///     [Segment::new("Hello World!", None)]
+    /// );
+    /// ```
+    pub fn simplify(segments: &[Self]) -> Vec<Self> {
+        let mut simplified: Vec<Self> = vec![];
+
+        for segment in segments {
+            match simplified.last_mut() {
+                Some(last)
+                    if last.style == segment.style
+                        && !last.is_control()
+                        && !segment.is_control() =>
+                {
+                    last.text.push_str(&segment.text);
+                },
+                _ => simplified.push(segment.clone()),
+            }
+        }
+
+        simplified
+    }
+
+    /// Remove all links from an iterable of styles.
+    pub fn strip_links(segments: &[Self]) -> Vec<Self> {
+        segments
+            .iter()
+            .map(|segment| {
+                let mut segment = segment.clone();
+                if let Some(style) = &mut segment.style {
+                    style.link = None;
+                }
+
+                segment
+            })
+            .collect()
+    }
+
+    /// Remove all styles from an iterable of segments.
+    pub fn strip_styles(segments: &[Self]) -> Vec<Self> {
+        segments
+            .iter()
+            .map(|segment| Self {
+                style: None,
+                ..segment.clone()
+            })
+            .collect()
+    }
+
+    /// Divides an iterable of segments in to portions.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - Segments to divide.
+    /// * `cuts` - Cell positions where to divide, in ascending order.
+    ///
+    /// A cut lower than the one before it gives an empty portion.
+    ///
+    /// # Returns
+    ///
+    /// A vector of segments for each portion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::segment::Segment;
+    ///
+    /// let segments = [Segment::new("Hello", None), Segment::new(" World!", None)];
+    ///
+    /// assert_eq!(
+    ///     Segment::divide(&segments, &[4, 20]),
+    ///     vec![
+    ///         vec![Segment::new("Hell", None)],
+    ///         vec![Segment::new("o", None), Segment::new(" World!", None)],
+    ///     ]
+    /// );
+    /// ```
+    pub fn divide(segments: &[Self], cuts: &[u32]) -> Vec<Vec<Self>> {
+        let mut portions = vec![];
+        let mut cuts = cuts.iter().copied();
+
+        let mut cut = loop {
+            match cuts.next() {
+                None => return portions,
+                Some(0) => portions.push(vec![]),
+                Some(cut) => break cut,
+            }
+        };
+
+        let mut split_segments = vec![];
+        let mut position = 0;
+
+        for segment in segments {
+            let mut segment = segment.clone();
+
+            while !segment.text.is_empty() {
+                if cut <= position {
+                    portions.push(vec![]);
+
+                    match cuts.next() {
+                        Some(next_cut) => cut = next_cut,
+                        None => return portions,
+                    }
+                    continue;
+                }
+
+                let end_position = position + segment.cell_length();
+
+                if end_position < cut {
+                    split_segments.push(segment);
+                    position = end_position;
+                    break;
+                }
+
+                if end_position == cut {
+                    split_segments.push(segment);
+                    portions.push(std::mem::take(&mut split_segments));
+                    position = end_position;
+
+                    match cuts.next() {
+                        Some(next_cut) => cut = next_cut,
+                        None => return portions,
+                    }
+                    break;
+                }
+
+                let (before, after) = segment.split_cells(cut - position);
+                split_segments.push(before);
+                portions.push(std::mem::take(&mut split_segments));
+                segment = after;
+                position = cut;
+
+                match cuts.next() {
+                    Some(next_cut) => cut = next_cut,
+                    None => return portions,
+                }
+            }
+        }
+
+        portions.push(split_segments);
+
+        portions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn segment(text: &str) -> Segment { Segment::new(text, None) }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(Segment::line(), segment("\n"));
+    }
+
+    #[test]
+    fn test_control() {
+        let control = Segment::new_control(vec![ControlCode::Bell]);
+
+        assert!(control.is_control());
+        assert_eq!(control.cell_length(), 0);
+        assert!(!segment("foo").is_control());
+    }
+
+    #[rstest]
+    #[case(ControlCode::Bell, "\x07")]
+    #[case(ControlCode::CursorUp(2), "\x1b[2A")]
+    #[case(ControlCode::CursorMoveToColumn(0), "\x1b[1G")]
+    #[case(ControlCode::CursorMoveTo(2, 4), "\x1b[5;3H")]
+    #[case(ControlCode::SetWindowTitle("foo".into()), "\x1b]0;foo\x07")]
+    fn test_control_code_display(#[case] control: ControlCode, #[case] result: &str) {
+        assert_eq!(control.to_string(), result);
+    }
+
+    #[test]
+    fn test_apply_style() {
+        let bold = Style::parse("bold").unwrap();
+        let italic = Style::parse("italic").unwrap();
+        let segments = [segment("foo"), Segment::new("bar", Some(bold.clone()))];
+
+        assert_eq!(Segment::apply_style(&segments, None, None), segments);
+        assert_eq!(
+            Segment::apply_style(&segments, Some(&italic), None),
+            [
+                Segment::new("foo", Some(italic.clone())),
+                Segment::new("bar", Some(Style::parse("bold italic").unwrap())),
+            ]
+        );
+        assert_eq!(
+            Segment::apply_style(&segments, None, Some(&Style::parse("not bold").unwrap())),
+            [
+                Segment::new("foo", Some(Style::parse("not bold").unwrap())),
+                Segment::new("bar", Some(Style::parse("not bold").unwrap())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_control() {
+        let control = Segment::new_control(vec![ControlCode::Home]);
+        let segments = [segment("foo"), control.clone()];
+
+        assert_eq!(Segment::filter_control(&segments, false), [segment("foo")]);
+        assert_eq!(Segment::filter_control(&segments, true), [control]);
+    }
+
+    #[test]
+    fn test_split_lines() {
+        assert_eq!(
+            Segment::split_lines(&[segment("Hello\nWorld")]),
+            vec![vec![segment("Hello")], vec![segment("World")]]
+        );
+        assert_eq!(
+            Segment::split_lines(&[segment("Hello\n"), segment("World\n")]),
+            vec![vec![segment("Hello")], vec![segment("World")]]
+        );
+    }
+
+    #[test]
+    fn test_split_and_crop_lines() {
+        assert_eq!(
+            Segment::split_and_crop_lines(
+                &[segment("Hello\nWorld!\n"), segment("foo")],
+                4,
+                None,
+                true,
+                true
+            ),
+            vec![
+                vec![segment("Hell"), segment("\n")],
+                vec![segment("Worl"), segment("\n")],
+                vec![segment("foo"), segment(" ")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_adjust_line_length() {
+        let foo = Style::parse("bold").unwrap();
+        let bar = Style::parse("italic").unwrap();
+
+        assert_eq!(
+            Segment::adjust_line_length(
+                &[Segment::new("Hello", Some(foo.clone()))],
+                10,
+                Some(&bar),
+                true
+            ),
+            [
+                Segment::new("Hello", Some(foo)),
+                Segment::new("     ", Some(bar))
+            ]
+        );
+        assert_eq!(
+            Segment::adjust_line_length(&[segment("H"), segment("ello, World!")], 5, None, true),
+            [segment("H"), segment("ello")]
+        );
+        assert_eq!(
+            Segment::adjust_line_length(&[segment("Hello")], 5, None, true),
+            [segment("Hello")]
+        );
+        assert_eq!(
+            Segment::adjust_line_length(&[segment("Hello")], 10, None, false),
+            [segment("Hello")]
+        );
+    }
+
+    #[test]
+    fn test_get_line_length() {
+        assert_eq!(
+            Segment::get_line_length(&[segment("foo"), segment("bar")]),
+            6
+        );
+    }
+
+    #[test]
+    fn test_get_shape() {
+        assert_eq!(Segment::get_shape(&[vec![segment("Hello")]]), (5, 1));
+        assert_eq!(
+            Segment::get_shape(&[vec![segment("Hello")], vec![segment("World!")]]),
+            (6, 2)
+        );
+    }
+
+    #[test]
+    fn test_set_shape() {
+        assert_eq!(
+            Segment::set_shape(&[vec![segment("Hello")]], 10, None, None, false),
+            vec![vec![segment("Hello"), segment("     ")]]
+        );
+        assert_eq!(
+            Segment::set_shape(&[vec![segment("Hello")]], 10, Some(2), None, false),
+            vec![
+                vec![segment("Hello"), segment("     ")],
+                vec![segment(" ".repeat(10).as_str())],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simplify() {
+        let red = Style::parse("red").unwrap();
+        let blue = Style::parse("blue").unwrap();
+
+        assert_eq!(
+            Segment::simplify(&[segment("Hello"), segment(" "), segment("World!")]),
+            [segment("Hello World!")]
+        );
+        assert_eq!(
+            Segment::simplify(&[
+                Segment::new("Hello", Some(red.clone())),
+                Segment::new(" ", Some(red.clone())),
+                Segment::new("World!", Some(blue.clone())),
+            ]),
+            [
+                Segment::new("Hello ", Some(red)),
+                Segment::new("World!", Some(blue))
+            ]
+        );
+        assert_eq!(Segment::simplify(&[]), []);
+    }
+
+    #[test]
+    fn test_strip_links() {
+        let segments = [Segment::new(
+            "foo",
+            Some(Style::parse("bold link https://example.org").unwrap()),
+        )];
+
+        assert_eq!(
+            Segment::strip_links(&segments),
+            [Segment::new("foo", Some(Style::parse("bold").unwrap()))]
+        );
+    }
+
+    #[test]
+    fn test_strip_styles() {
+        let segments = [Segment::new("foo", Some(Style::parse("bold").unwrap()))];
+
+        assert_eq!(Segment::strip_styles(&segments), [segment("foo")]);
+    }
+
+    #[test]
+    fn test_divide() {
+        let bold = Some(Style::parse("bold").unwrap());
+        let italic = Some(Style::parse("italic").unwrap());
+        let segments = [
+            Segment::new("Hello", bold.clone()),
+            Segment::new(" World!", italic.clone()),
+        ];
+
+        assert_eq!(Segment::divide(&segments, &[]), Vec::<Vec<Segment>>::new());
+        assert_eq!(Segment::divide(&[], &[1]), vec![vec![]]);
+        assert_eq!(
+            Segment::divide(&segments, &[1]),
+            vec![vec![Segment::new("H", bold.clone())]]
+        );
+        assert_eq!(
+            Segment::divide(&segments, &[1, 2]),
+            vec![
+                vec![Segment::new("H", bold.clone())],
+                vec![Segment::new("e", bold.clone())]
+            ]
+        );
+        assert_eq!(
+            Segment::divide(&segments, &[1, 2, 12]),
+            vec![
+                vec![Segment::new("H", bold.clone())],
+                vec![Segment::new("e", bold.clone())],
+                vec![
+                    Segment::new("llo", bold.clone()),
+                    Segment::new(" World!", italic.clone())
+                ],
+            ]
+        );
+        assert_eq!(
+            Segment::divide(&segments, &[4, 20]),
+            vec![
+                vec![Segment::new("Hell", bold.clone())],
+                vec![
+                    Segment::new("o", bold.clone()),
+                    Segment::new(" World!", italic.clone())
+                ],
+            ]
+        );
+        assert_eq!(
+            Segment::divide(&segments, &[4, 2, 4, 6]),
+            vec![
+                vec![Segment::new("Hell", bold.clone())],
+                vec![],
+                vec![],
+                vec![Segment::new("o", bold), Segment::new(" ", italic)],
+            ]
+        );
+        assert_eq!(
+            Segment::divide(&[segment("Hello World")], &[4, 2]),
+            vec![vec![segment("Hell")], vec![]]
+        );
+    }
+
+    #[rstest]
+    #[case("XX", 4, "XX", "")]
+    #[case("X", 1, "X", "")]
+    #[case("💩", 1, " ", " ")]
+    #[case("XY", 1, "X", "Y")]
+    #[case("💩X", 1, " ", " X")]
+    #[case("💩💩", 1, " ", " 💩")]
+    #[case("X💩Y", 2, "X ", " Y")]
+    #[case("X💩YZ", 2, "X ", " YZ")]
+    #[case("X💩💩Z", 2, "X ", " 💩Z")]
+    #[case("X💩💩Z", 3, "X💩", "💩Z")]
+    #[case("X💩💩Z", 4, "X💩 ", " Z")]
+    #[case("X💩💩Z", 5, "X💩💩", "Z")]
+    #[case("X💩💩Z", 6, "X💩💩Z", "")]
+    #[case("XYZABC💩💩", 6, "XYZABC", "💩💩")]
+    fn test_split_cells(
+        #[case] text: &str,
+        #[case] cut: u32,
+        #[case] before: &str,
+        #[case] after: &str,
+    ) {
+        assert_eq!(
+            segment(text).split_cells(cut),
+            (segment(before), segment(after))
+        );
+    }
+}
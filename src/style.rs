@@ -0,0 +1,676 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::Add;
+use std::str::FromStr;
+
+use crate::color::{Color, ColorParseError, ColorSystem, ColorType};
+use crate::color_triplet::{ColorTriplet, WCAG_AA_CONTRAST_RATIO};
+use crate::terminal_theme::{TerminalTheme, DEFAULT_TERMINAL_THEME};
+
+/// The style could not be parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StyleSyntaxError {
+    /// `on` wasn't followed by a color.
+    ExpectedColorAfterOn,
+    /// `not` wasn't followed by a word.
+    ExpectedAttributeAfterNot,
+    /// The word after `not` isn't a style attribute.
+    UnknownAttributeAfterNot(String),
+    /// `link` wasn't followed by an URL.
+    ExpectedUrlAfterLink,
+    /// The word after `on` isn't a valid color.
+    InvalidBackgroundColor(String, ColorParseError),
+    /// The word is neither a style attribute nor a valid color.
+    InvalidColor(String, ColorParseError),
+}
+
+impl Display for StyleSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExpectedColorAfterOn => write!(f, "color expected after 'on'"),
+            Self::ExpectedAttributeAfterNot => write!(f, "expected style attribute after 'not'"),
+            Self::UnknownAttributeAfterNot(word) => {
+                write!(f, "expected style attribute after 'not', found {word:?}")
+            },
+            Self::ExpectedUrlAfterLink => write!(f, "URL expected after 'link'"),
+            Self::InvalidBackgroundColor(word, error) => {
+                write!(f, "unable to parse {word:?} as background color; {error}")
+            },
+            Self::InvalidColor(word, error) => {
+                write!(f, "unable to parse {word:?} as color; {error}")
+            },
+        }
+    }
+}
+
+impl Error for StyleSyntaxError {}
+
+/// The colors of a style don't contrast enough to be easily read.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LowContrastError {
+    /// The color of the text.
+    pub foreground: ColorTriplet,
+    /// The color behind the text.
+    pub background: ColorTriplet,
+    /// The contrast ratio between the two colors.
+    pub contrast_ratio: f64,
+}
+
+impl Display for LowContrastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "contrast ratio of {:.2}:1 between {} and {} is below the WCAG AA minimum of \
+             {WCAG_AA_CONTRAST_RATIO}:1",
+            self.contrast_ratio,
+            self.foreground.hex(),
+            self.background.hex()
+        )
+    }
+}
+
+impl Error for LowContrastError {}
+
+/// A terminal style.
+///
+/// A terminal style consists of a color (`color`), a background color
+/// (`bgcolor`), and a number of attributes, such as bold, italic etc. An
+/// attribute set to `None` is inherited when styles are combined.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Style {
+    /// Color of terminal text.
+    pub color: Option<Color>,
+    /// Color of terminal background.
+    pub bgcolor: Option<Color>,
+    /// Enable bold text.
+    pub bold: Option<bool>,
+    /// Enable dim text.
+    pub dim: Option<bool>,
+    /// Enable italic text.
+    pub italic: Option<bool>,
+    /// Enable underlined text.
+    pub underline: Option<bool>,
+    /// Enable blinking text.
+    pub blink: Option<bool>,
+    /// Enable reverse text.
+    pub reverse: Option<bool>,
+    /// Enable concealed text.
+    pub conceal: Option<bool>,
+    /// Enable strikethrough text.
+    pub strike: Option<bool>,
+    /// Link URL.
+    pub link: Option<String>,
+}
+
+impl Style {
+    /// Check if the style specifies nothing.
+    pub fn is_null(&self) -> bool { self == &Self::default() }
+
+    /// Get the style attributes, along with their names and SGR codes.
+    const fn attributes(&self) -> [(&'static str, &'static str, Option<bool>); 8] {
+        [
+            ("bold", "1", self.bold),
+            ("dim", "2", self.dim),
+            ("italic", "3", self.italic),
+            ("underline", "4", self.underline),
+            ("blink", "5", self.blink),
+            ("reverse", "7", self.reverse),
+            ("conceal", "8", self.conceal),
+            ("strike", "9", self.strike),
+        ]
+    }
+
+    /// Get a mutable reference to an attribute from its name or alias.
+    fn attribute_mut(&mut self, name: &str) -> Option<&mut Option<bool>> {
+        Some(match name {
+            "bold" | "b" => &mut self.bold,
+            "dim" | "d" => &mut self.dim,
+            "italic" | "i" => &mut self.italic,
+            "underline" | "u" => &mut self.underline,
+            "blink" => &mut self.blink,
+            "reverse" | "r" => &mut self.reverse,
+            "conceal" | "c" => &mut self.conceal,
+            "strike" | "s" => &mut self.strike,
+            _ => return None,
+        })
+    }
+
+    /// Parse a style definition.
+    ///
+    /// # Arguments
+    ///
+    /// * `style_definition` - A string containing a style, such as `"bold red
+    ///   on white"`.
+    ///
+    /// # Errors
+    ///
+    /// [`StyleSyntaxError`] if the style definition is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::Color;
+    /// use wealthy::style::Style;
+    ///
+    /// let style = Style::parse("bold red on white").unwrap();
+    ///
+    /// assert_eq!(style.bold, Some(true));
+    /// assert_eq!(style.color, Some(Color::parse("red").unwrap()));
+    /// assert_eq!(style.bgcolor, Some(Color::parse("white").unwrap()));
+    /// assert!(Style::parse("bold on").is_err());
+    /// ```
+    pub fn parse(style_definition: &str) -> Result<Self, StyleSyntaxError> {
+        if style_definition.trim() == "none" || style_definition.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut style = Self::default();
+        let mut words = style_definition.split_whitespace();
+
+        while let Some(original_word) = words.next() {
+            let word = original_word.to_lowercase();
+
+            match word.as_str() {
+                "on" => {
+                    let word = words.next().ok_or(StyleSyntaxError::ExpectedColorAfterOn)?;
+                    style.bgcolor = Some(Color::parse(word).map_err(|error| {
+                        StyleSyntaxError::InvalidBackgroundColor(word.into(), error)
+                    })?);
+                },
+                "not" => {
+                    let word = words
+                        .next()
+                        .ok_or(StyleSyntaxError::ExpectedAttributeAfterNot)?
+                        .to_lowercase();
+                    *style
+                        .attribute_mut(&word)
+                        .ok_or(StyleSyntaxError::UnknownAttributeAfterNot(word))? = Some(false);
+                },
+                "link" => {
+                    let word = words.next().ok_or(StyleSyntaxError::ExpectedUrlAfterLink)?;
+                    style.link = Some(word.into());
+                },
+                _ => {
+                    if let Some(attribute) = style.attribute_mut(&word) {
+                        *attribute = Some(true);
+                    } else {
+                        style.color = Some(Color::parse(&word).map_err(|error| {
+                            StyleSyntaxError::InvalidColor(original_word.into(), error)
+                        })?);
+                    }
+                },
+            }
+        }
+
+        Ok(style)
+    }
+
+    /// Generate the ANSI codes for this style, without the escape sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `color_system` - The color system to downgrade the colors to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::ColorSystem;
+    /// use wealthy::style::Style;
+    ///
+    /// let style = Style::parse("bold red on #ffffff").unwrap();
+    ///
+    /// assert_eq!(
+    ///     style.ansi_codes(ColorSystem::TrueColor),
+    ///     "1;31;48;2;255;255;255"
+    /// );
+    /// assert_eq!(style.ansi_codes(ColorSystem::EightBit), "1;31;48;5;231");
+    /// ```
+    pub fn ansi_codes(&self, color_system: ColorSystem) -> String {
+        let mut codes: Vec<String> = self
+            .attributes()
+            .into_iter()
+            .filter(|(_, _, value)| value == &Some(true))
+            .map(|(_, code, _)| code.into())
+            .collect();
+
+        if let Some(color) = &self.color {
+            codes.extend(color.downgrade(color_system).get_ansi_codes(true));
+        }
+        if let Some(bgcolor) = &self.bgcolor {
+            codes.extend(bgcolor.downgrade(color_system).get_ansi_codes(false));
+        }
+
+        codes.join(";")
+    }
+
+    /// Render the ANSI codes for the style around some text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to style.
+    /// * `color_system` - The color system to render to, or `None` to render
+    ///   the text as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::ColorSystem;
+    /// use wealthy::style::Style;
+    ///
+    /// let style = Style::parse("bold red").unwrap();
+    ///
+    /// assert_eq!(
+    ///     style.render("foo", Some(ColorSystem::TrueColor)),
+    ///     "\x1b[1;31mfoo\x1b[0m"
+    /// );
+    /// assert_eq!(style.render("foo", None), "foo");
+    /// ```
+    pub fn render(&self, text: &str, color_system: Option<ColorSystem>) -> String {
+        let Some(color_system) = color_system else {
+            return text.into();
+        };
+        if text.is_empty() {
+            return String::new();
+        }
+
+        let attributes = self.ansi_codes(color_system);
+        let rendered = if attributes.is_empty() {
+            text.into()
+        } else {
+            format!("\x1b[{attributes}m{text}\x1b[0m")
+        };
+
+        match &self.link {
+            Some(link) => format!("\x1b]8;;{link}\x1b\\{rendered}\x1b]8;;\x1b\\"),
+            None => rendered,
+        }
+    }
+
+    /// Resolve the foreground color through a theme, using the bright
+    /// version of the 8 normal intensity colors for bold text if the theme
+    /// has [`TerminalTheme::bold_is_bright`] set.
+    pub(crate) fn theme_color(&self, theme: &TerminalTheme) -> Option<ColorTriplet> {
+        let color = self.color.as_ref()?;
+
+        match (color.color_type, color.number) {
+            (ColorType::Standard | ColorType::EightBit, Some(number @ 0..=7))
+                if theme.bold_is_bright && self.bold == Some(true) =>
+            {
+                Some(theme.ansi_colors()[usize::from(number) + 8])
+            },
+            _ => Some(color.get_truecolor(Some(theme), true)),
+        }
+    }
+
+    /// Get the colors that text in this style is displayed with on a theme,
+    /// after reversing and dimming. `None` stands for the theme's default
+    /// color.
+    fn theme_colors(&self, theme: &TerminalTheme) -> (Option<ColorTriplet>, Option<ColorTriplet>) {
+        let (mut color, mut bgcolor) = (
+            self.theme_color(theme),
+            self.bgcolor
+                .as_ref()
+                .map(|bgcolor| bgcolor.get_truecolor(Some(theme), false)),
+        );
+        if self.reverse == Some(true) {
+            (color, bgcolor) = (bgcolor, color);
+        }
+        if self.dim == Some(true) {
+            let foreground = color.unwrap_or(theme.foreground_color);
+            color = Some(foreground.blend(&theme.background_color, 0.5));
+        }
+
+        (color, bgcolor)
+    }
+
+    /// Get the WCAG 2 contrast ratio between the text and background colors of
+    /// this style.
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - The theme to resolve colors with, or `None` for the default
+    ///   theme.
+    pub fn contrast_ratio(&self, theme: Option<&TerminalTheme>) -> f64 {
+        let (foreground, background) = self.contrast_colors(theme);
+
+        foreground.contrast_ratio(&background)
+    }
+
+    /// Get the text and background colors of this style on a theme.
+    fn contrast_colors(&self, theme: Option<&TerminalTheme>) -> (ColorTriplet, ColorTriplet) {
+        let theme = theme.unwrap_or(&DEFAULT_TERMINAL_THEME);
+        let (color, bgcolor) = self.theme_colors(theme);
+
+        (
+            color.unwrap_or(theme.foreground_color),
+            bgcolor.unwrap_or(theme.background_color),
+        )
+    }
+
+    /// Check that text in this style is readable, meeting the WCAG AA contrast
+    /// ratio on a theme.
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - The theme to resolve colors with, or `None` for the default
+    ///   theme.
+    ///
+    /// # Errors
+    ///
+    /// [`LowContrastError`] if the contrast ratio is below
+    /// [`WCAG_AA_CONTRAST_RATIO`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::style::Style;
+    ///
+    /// assert!(Style::parse("black on white")
+    ///     .unwrap()
+    ///     .check_contrast(None)
+    ///     .is_ok());
+    /// assert_eq!(
+    ///     Style::parse("yellow on white")
+    ///         .unwrap()
+    ///         .check_contrast(None)
+    ///         .unwrap_err()
+    ///         .to_string(),
+    ///     "contrast ratio of 2.31:1 between #808000 and #c0c0c0 is below the WCAG AA minimum of \
+    ///      4.5:1"
+    /// );
+    /// ```
+    pub fn check_contrast(&self, theme: Option<&TerminalTheme>) -> Result<(), LowContrastError> {
+        let (foreground, background) = self.contrast_colors(theme);
+        let contrast_ratio = foreground.contrast_ratio(&background);
+
+        if contrast_ratio < WCAG_AA_CONTRAST_RATIO {
+            return Err(LowContrastError {
+                foreground,
+                background,
+                contrast_ratio,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get a CSS style rule for this style.
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - The theme to resolve colors with, or `None` for the default
+    ///   theme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::style::Style;
+    ///
+    /// assert_eq!(
+    ///     Style::parse("bold red").unwrap().get_html_style(None),
+    ///     "color: #800000; text-decoration-color: #800000; font-weight: bold"
+    /// );
+    /// ```
+    pub fn get_html_style(&self, theme: Option<&TerminalTheme>) -> String {
+        let theme = theme.unwrap_or(&DEFAULT_TERMINAL_THEME);
+        let mut css = vec![];
+
+        let (color, bgcolor) = self.theme_colors(theme);
+        if let Some(color) = color {
+            css.push(format!("color: {}", color.hex()));
+            css.push(format!("text-decoration-color: {}", color.hex()));
+        }
+        if let Some(bgcolor) = bgcolor {
+            css.push(format!("background-color: {}", bgcolor.hex()));
+        }
+        for (attribute, rule) in [
+            (self.bold, "font-weight: bold"),
+            (self.italic, "font-style: italic"),
+            (self.underline, "text-decoration: underline"),
+            (self.strike, "text-decoration: line-through"),
+        ] {
+            if attribute == Some(true) {
+                css.push(rule.into());
+            }
+        }
+
+        css.join("; ")
+    }
+}
+
+impl Add for Style {
+    type Output = Self;
+
+    /// Combine two styles, with attributes of `rhs` taking precedence.
+    fn add(self, rhs: Self) -> Self::Output { &self + &rhs }
+}
+
+impl Add<&Style> for &Style {
+    type Output = Style;
+
+    fn add(self, rhs: &Style) -> Self::Output {
+        Style {
+            color: rhs.color.clone().or_else(|| self.color.clone()),
+            bgcolor: rhs.bgcolor.clone().or_else(|| self.bgcolor.clone()),
+            bold: rhs.bold.or(self.bold),
+            dim: rhs.dim.or(self.dim),
+            italic: rhs.italic.or(self.italic),
+            underline: rhs.underline.or(self.underline),
+            blink: rhs.blink.or(self.blink),
+            reverse: rhs.reverse.or(self.reverse),
+            conceal: rhs.conceal.or(self.conceal),
+            strike: rhs.strike.or(self.strike),
+            link: rhs.link.clone().or_else(|| self.link.clone()),
+        }
+    }
+}
+
+impl FromStr for Style {
+    type Err = StyleSyntaxError;
+
+    fn from_str(style_definition: &str) -> Result<Self, Self::Err> { Self::parse(style_definition) }
+}
+
+impl Display for Style {
+    /// Re-generate a style definition from its attributes.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut attributes: Vec<String> = self
+            .attributes()
+            .into_iter()
+            .filter_map(|(name, _, value)| match value? {
+                true => Some(name.into()),
+                false => Some(format!("not {name}")),
+            })
+            .collect();
+
+        if let Some(color) = &self.color {
+            attributes.push(color.name.clone());
+        }
+        if let Some(bgcolor) = &self.bgcolor {
+            attributes.push(format!("on {}", bgcolor.name));
+        }
+        if let Some(link) = &self.link {
+            attributes.push(format!("link {link}"));
+        }
+
+        if attributes.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", attributes.join(" "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::terminal_theme::MONOKAI;
+
+    #[rstest]
+    #[case("", "none")]
+    #[case("none", "none")]
+    #[case("bold", "bold")]
+    #[case("not bold", "not bold")]
+    #[case(
+        "b i u s r c d blink",
+        "bold dim italic underline blink reverse conceal strike"
+    )]
+    #[case("bold red on black", "bold red on black")]
+    #[case("Bold RED on #FF0000", "bold red on #ff0000")]
+    #[case("link https://example.org", "link https://example.org")]
+    #[case("italic not bold color(100)", "not bold italic color(100)")]
+    fn test_parse_display(#[case] style_definition: &str, #[case] result: &str) {
+        assert_eq!(Style::parse(style_definition).unwrap().to_string(), result);
+    }
+
+    #[rstest]
+    #[case("on", StyleSyntaxError::ExpectedColorAfterOn)]
+    #[case("not", StyleSyntaxError::ExpectedAttributeAfterNot)]
+    #[case("not red", StyleSyntaxError::UnknownAttributeAfterNot("red".into()))]
+    #[case("link", StyleSyntaxError::ExpectedUrlAfterLink)]
+    #[case("on foo", StyleSyntaxError::InvalidBackgroundColor("foo".into(), ColorParseError::InvalidColor("foo".into())))]
+    #[case("bold Foo", StyleSyntaxError::InvalidColor("Foo".into(), ColorParseError::InvalidColor("foo".into())))]
+    fn test_parse_error(#[case] style_definition: &str, #[case] error: StyleSyntaxError) {
+        assert_eq!(Style::parse(style_definition), Err(error));
+    }
+
+    #[test]
+    fn test_add() {
+        let style = Style::parse("bold red on white").unwrap()
+            + Style::parse("not bold blue link foo").unwrap();
+
+        assert_eq!(
+            style,
+            Style::parse("not bold blue on white link foo").unwrap()
+        );
+        assert_eq!(Style::default() + style.clone(), style);
+        assert_eq!(style.clone() + Style::default(), style);
+    }
+
+    #[rstest]
+    #[case("", Some(ColorSystem::TrueColor), "foo")]
+    #[case("bold", None, "foo")]
+    #[case("bold red", Some(ColorSystem::TrueColor), "\x1b[1;31mfoo\x1b[0m")]
+    #[case("not bold red", Some(ColorSystem::TrueColor), "\x1b[31mfoo\x1b[0m")]
+    #[case(
+        "#ff0000 on #0000ff",
+        Some(ColorSystem::TrueColor),
+        "\x1b[38;2;255;0;0;48;2;0;0;255mfoo\x1b[0m"
+    )]
+    #[case(
+        "#ff0000 on #0000ff",
+        Some(ColorSystem::EightBit),
+        "\x1b[38;5;196;48;5;21mfoo\x1b[0m"
+    )]
+    #[case(
+        "#ff0000 on #0000ff",
+        Some(ColorSystem::Standard),
+        "\x1b[31;44mfoo\x1b[0m"
+    )]
+    #[case(
+        "link https://example.org",
+        Some(ColorSystem::TrueColor),
+        "\x1b]8;;https://example.org\x1b\\foo\x1b]8;;\x1b\\"
+    )]
+    fn test_render(
+        #[case] style_definition: &str,
+        #[case] color_system: Option<ColorSystem>,
+        #[case] result: &str,
+    ) {
+        assert_eq!(
+            Style::parse(style_definition)
+                .unwrap()
+                .render("foo", color_system),
+            result
+        );
+    }
+
+    #[test]
+    fn test_render_empty() {
+        assert_eq!(
+            Style::parse("bold")
+                .unwrap()
+                .render("", Some(ColorSystem::TrueColor)),
+            ""
+        );
+    }
+
+    #[rstest]
+    #[case("none", "")]
+    #[case("#123456", "color: #123456; text-decoration-color: #123456")]
+    #[case("reverse red", "background-color: #800000")]
+    #[case("dim", "color: #7f7f7f; text-decoration-color: #7f7f7f")]
+    #[case("dim red", "color: #bf7f7f; text-decoration-color: #bf7f7f")]
+    #[case("on default", "background-color: #ffffff")]
+    #[case(
+        "italic underline strike",
+        "font-style: italic; text-decoration: underline; text-decoration: line-through"
+    )]
+    fn test_get_html_style(#[case] style_definition: &str, #[case] result: &str) {
+        assert_eq!(
+            Style::parse(style_definition).unwrap().get_html_style(None),
+            result
+        );
+    }
+
+    #[test]
+    fn test_get_html_style_bold_is_bright() {
+        let theme = DEFAULT_TERMINAL_THEME.clone().with_bold_is_bright(true);
+
+        assert_eq!(
+            Style::parse("bold red")
+                .unwrap()
+                .get_html_style(Some(&theme)),
+            "color: #ff0000; text-decoration-color: #ff0000; font-weight: bold"
+        );
+        assert_eq!(
+            Style::parse("red").unwrap().get_html_style(Some(&theme)),
+            "color: #800000; text-decoration-color: #800000"
+        );
+        assert_eq!(
+            Style::parse("bold color(100)")
+                .unwrap()
+                .get_html_style(Some(&theme)),
+            "color: #878700; text-decoration-color: #878700; font-weight: bold"
+        );
+    }
+
+    #[rstest]
+    #[case("", 21.0)]
+    #[case("#ffffff on #000000", 21.0)]
+    #[case("reverse", 21.0)]
+    #[case("black on black", 1.0)]
+    #[case("#767676", 4.542)]
+    #[case("dim", 4.004)]
+    fn test_contrast_ratio(#[case] style: &str, #[case] result: f64) {
+        let contrast_ratio = Style::parse(style).unwrap().contrast_ratio(None);
+
+        assert!((contrast_ratio - result).abs() < 1e-3, "{contrast_ratio}");
+    }
+
+    #[test]
+    fn test_check_contrast() {
+        assert_eq!(
+            Style::parse("#767676").unwrap().check_contrast(None),
+            Ok(())
+        );
+        assert_eq!(
+            Style::parse("#777777").unwrap().check_contrast(None),
+            Err(LowContrastError {
+                foreground: ColorTriplet::new(119, 119, 119),
+                background: ColorTriplet::new(255, 255, 255),
+                contrast_ratio: ColorTriplet::new(119, 119, 119)
+                    .contrast_ratio(&ColorTriplet::new(255, 255, 255)),
+            })
+        );
+        // Bright black is readable on Monokai, but not the normal black it
+        // replaces when bold isn't bright.
+        let theme = MONOKAI.clone().with_bold_is_bright(true);
+        let style = Style::parse("bold black").unwrap();
+
+        assert!(style.check_contrast(Some(&MONOKAI)).is_err());
+        assert!(style.contrast_ratio(Some(&theme)) > style.contrast_ratio(Some(&MONOKAI)));
+    }
+}
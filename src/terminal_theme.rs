@@ -1,344 +1,813 @@
-#![allow(dead_code)]
+use std::error::Error;
+use std::fmt::{self, Display};
 
 use lazy_static::lazy_static;
 
-use crate::color_triplet::ColorTriplet;
+use crate::color_triplet::{ColorTriplet, WCAG_AA_CONTRAST_RATIO};
 use crate::palette::Palette;
+#[cfg(feature = "theme-files")]
+pub use crate::theme_files::ThemeParseError;
+
+/// The colors of a [`TerminalTheme`] don't make a 16 color palette.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TerminalThemeError {
+    /// There aren't exactly 8 normal intensity colors, with the number given.
+    NormalColorCount(usize),
+    /// There aren't exactly 8 bright colors, with the number given.
+    BrightColorCount(usize),
+}
+
+impl Display for TerminalThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NormalColorCount(count) => {
+                write!(f, "expected 8 normal intensity colors, got {count}")
+            },
+            Self::BrightColorCount(count) => write!(f, "expected 8 bright colors, got {count}"),
+        }
+    }
+}
+
+impl Error for TerminalThemeError {}
 
 /// A color theme used when exporting console content.
-#[derive(Debug)]
-pub(crate) struct TerminalTheme {
-    pub(crate) background_color: ColorTriplet,
-    pub(crate) foreground_color: ColorTriplet,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TerminalTheme {
+    /// The background color.
+    pub background_color: ColorTriplet,
+    /// The foreground (text) color.
+    pub foreground_color: ColorTriplet,
     pub(crate) ansi_colors: Palette<16>,
+    /// The cursor color, if the theme has one.
+    pub cursor_color: Option<ColorTriplet>,
+    /// The background color of selected text, if the theme has one.
+    pub selection_background: Option<ColorTriplet>,
+    /// The foreground color of selected text, if the theme has one.
+    pub selection_foreground: Option<ColorTriplet>,
+    /// Show bold text in the bright version of the 8 normal intensity colors.
+    pub bold_is_bright: bool,
 }
 
 impl TerminalTheme {
     /// Get a new [`TerminalTheme`].
     ///
     /// # Arguments
+    ///
     /// * `background` - The background color.
     /// * `foreground` - The foreground (text) color.
     /// * `normal` - A list of 8 normal intensity colors.
     /// * `bright`- A list of 8 bright colors, or none to repeat normal
     ///   intensity.
-    pub(crate) fn new(
+    ///
+    /// # Errors
+    ///
+    /// [`TerminalThemeError`] if there aren't exactly 8 normal and 8 bright
+    /// colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::terminal_theme::{TerminalTheme, TerminalThemeError};
+    ///
+    /// let normal = vec![(0, 0, 0); 8];
+    ///
+    /// assert!(TerminalTheme::try_new((0, 0, 0), (255, 255, 255), normal.clone(), None).is_ok());
+    /// assert_eq!(
+    ///     TerminalTheme::try_new((0, 0, 0), (255, 255, 255), normal, Some(vec![])),
+    ///     Err(TerminalThemeError::BrightColorCount(0))
+    /// );
+    /// ```
+    pub fn try_new(
         background_color: (u8, u8, u8),
         foreground_color: (u8, u8, u8),
-        mut normal: Vec<(u8, u8, u8)>,
+        normal: Vec<(u8, u8, u8)>,
+        bright: Option<Vec<(u8, u8, u8)>>,
+    ) -> Result<Self, TerminalThemeError> {
+        if normal.len() != 8 {
+            return Err(TerminalThemeError::NormalColorCount(normal.len()));
+        }
+        let bright = bright.unwrap_or_else(|| normal.clone());
+        if bright.len() != 8 {
+            return Err(TerminalThemeError::BrightColorCount(bright.len()));
+        }
+
+        let triplet = |(red, green, blue)| ColorTriplet::new(red, green, blue);
+        let ansi_colors = std::array::from_fn(|index| {
+            triplet(if index < 8 {
+                normal[index]
+            } else {
+                bright[index - 8]
+            })
+        });
+
+        Ok(Self::from_triplets(
+            triplet(background_color),
+            triplet(foreground_color),
+            ansi_colors,
+        ))
+    }
+
+    /// Get a new [`TerminalTheme`].
+    ///
+    /// # Arguments
+    ///
+    /// * `background` - The background color.
+    /// * `foreground` - The foreground (text) color.
+    /// * `normal` - A list of 8 normal intensity colors.
+    /// * `bright`- A list of 8 bright colors, or none to repeat normal
+    ///   intensity.
+    ///
+    /// # Panics
+    ///
+    /// If there aren't exactly 8 normal and 8 bright colors. Use
+    /// [`TerminalTheme::try_new`] to handle this instead.
+    pub fn new(
+        background_color: (u8, u8, u8),
+        foreground_color: (u8, u8, u8),
+        normal: Vec<(u8, u8, u8)>,
         bright: Option<Vec<(u8, u8, u8)>>,
     ) -> Self {
-        normal.append(&mut bright.unwrap_or_else(|| normal.clone()));
+        Self::try_new(background_color, foreground_color, normal, bright)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Set the cursor color.
+    #[must_use]
+    pub fn with_cursor_color(self, cursor_color: ColorTriplet) -> Self {
+        Self {
+            cursor_color: Some(cursor_color),
+            ..self
+        }
+    }
 
+    /// Set the colors of selected text.
+    ///
+    /// # Arguments
+    ///
+    /// * `background` - The background color of selected text.
+    /// * `foreground` - The foreground color of selected text, or `None` to
+    ///   leave it unchanged.
+    #[must_use]
+    pub fn with_selection_colors(
+        self,
+        background: ColorTriplet,
+        foreground: Option<ColorTriplet>,
+    ) -> Self {
         Self {
-            background_color: ColorTriplet::new(
-                background_color.0,
-                background_color.1,
-                background_color.2,
-            ),
-            foreground_color: ColorTriplet::new(
-                foreground_color.0,
-                foreground_color.1,
-                foreground_color.2,
-            ),
-            ansi_colors: Palette::new(normal.try_into().unwrap()),
+            selection_background: Some(background),
+            selection_foreground: foreground,
+            ..self
         }
     }
+
+    /// Show bold text in the bright version of the 8 normal intensity colors
+    /// (or not).
+    #[must_use]
+    pub fn with_bold_is_bright(self, bold_is_bright: bool) -> Self {
+        Self {
+            bold_is_bright,
+            ..self
+        }
+    }
+
+    /// Get a new [`TerminalTheme`] from color triplets.
+    pub(crate) const fn from_triplets(
+        background_color: ColorTriplet,
+        foreground_color: ColorTriplet,
+        ansi_colors: [ColorTriplet; 16],
+    ) -> Self {
+        Self {
+            background_color,
+            foreground_color,
+            ansi_colors: Palette {
+                colors: ansi_colors,
+            },
+            cursor_color: None,
+            selection_background: None,
+            selection_foreground: None,
+            bold_is_bright: false,
+        }
+    }
+
+    /// Get the 16 ANSI colors of the theme, the 8 normal intensity colors
+    /// followed by the 8 bright colors.
+    pub const fn ansi_colors(&self) -> &[ColorTriplet; 16] { &self.ansi_colors.colors }
+
+    /// Get a color for text that is readable over a background.
+    ///
+    /// The theme's foreground or background color is used, whichever contrasts
+    /// more with `background`. If neither meets [`WCAG_AA_CONTRAST_RATIO`],
+    /// black or white is used instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `background` - The background color of the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color_triplet::ColorTriplet;
+    /// use wealthy::terminal_theme::MONOKAI;
+    ///
+    /// assert_eq!(
+    ///     MONOKAI.readable_foreground(ColorTriplet::new(255, 255, 0)),
+    ///     MONOKAI.background_color
+    /// );
+    /// assert_eq!(
+    ///     MONOKAI.readable_foreground(ColorTriplet::new(0, 0, 128)),
+    ///     MONOKAI.foreground_color
+    /// );
+    /// ```
+    pub fn readable_foreground(&self, background: ColorTriplet) -> ColorTriplet {
+        let most_readable = |color1: ColorTriplet, color2: ColorTriplet| {
+            if color1.contrast_ratio(&background) >= color2.contrast_ratio(&background) {
+                color1
+            } else {
+                color2
+            }
+        };
+        let foreground = most_readable(self.foreground_color, self.background_color);
+
+        if foreground.contrast_ratio(&background) >= WCAG_AA_CONTRAST_RATIO {
+            foreground
+        } else {
+            most_readable(ColorTriplet::new(0, 0, 0), ColorTriplet::new(255, 255, 255))
+        }
+    }
+
+    /// Get a built-in theme by name.
+    ///
+    /// Names are case insensitive, and hyphens or spaces may be used in place
+    /// of underscores.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the theme, as listed by [`TerminalTheme::all`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::terminal_theme::{TerminalTheme, DRACULA, MATERIAL_OCEAN};
+    ///
+    /// assert_eq!(TerminalTheme::by_name("dracula"), Some(&*DRACULA));
+    /// assert_eq!(
+    ///     TerminalTheme::by_name("Material-Ocean"),
+    ///     Some(&*MATERIAL_OCEAN)
+    /// );
+    /// assert_eq!(TerminalTheme::by_name("solarized"), None);
+    /// ```
+    pub fn by_name(name: &str) -> Option<&'static Self> {
+        let name = name.trim().to_lowercase().replace(['-', ' '], "_");
+
+        THEMES
+            .iter()
+            .find(|(theme_name, _)| *theme_name == name)
+            .map(|(_, theme)| *theme)
+    }
+
+    /// Get the names of the built-in themes along with the themes.
+    pub fn all() -> &'static [(&'static str, &'static Self)] { THEMES.as_slice() }
 }
 
-lazy_static! {
-    static ref DEFAULT_TERMINAL_THEME: TerminalTheme = TerminalTheme::new(
+/// The raw colors of a built-in theme, as passed to [`TerminalTheme::new`].
+struct ThemeColors {
+    background: (u8, u8, u8),
+    foreground: (u8, u8, u8),
+    normal: &'static [(u8, u8, u8)],
+    bright: Option<&'static [(u8, u8, u8)]>,
+}
+
+impl ThemeColors {
+    /// Get a [`TerminalTheme`] from the colors.
+    fn theme(&self) -> TerminalTheme {
+        TerminalTheme::new(
+            self.background,
+            self.foreground,
+            self.normal.to_vec(),
+            self.bright.map(<[_]>::to_vec),
+        )
+    }
+}
+
+const DEFAULT_COLORS: ThemeColors = ThemeColors {
+    background: (255, 255, 255),
+    foreground: (0, 0, 0),
+    normal: &[
+        (0, 0, 0),
+        (128, 0, 0),
+        (0, 128, 0),
+        (128, 128, 0),
+        (0, 0, 128),
+        (128, 0, 128),
+        (0, 128, 128),
+        (192, 192, 192),
+    ],
+    bright: Some(&[
+        (128, 128, 128),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (0, 0, 255),
+        (255, 0, 255),
+        (0, 255, 255),
         (255, 255, 255),
+    ]),
+};
+
+const MONOKAI_COLORS: ThemeColors = ThemeColors {
+    background: (12, 12, 12),
+    foreground: (217, 217, 217),
+    normal: &[
+        (26, 26, 26),
+        (244, 0, 95),
+        (152, 224, 36),
+        (253, 151, 31),
+        (157, 101, 255),
+        (244, 0, 95),
+        (88, 209, 235),
+        (196, 197, 181),
+    ],
+    bright: Some(&[
+        (98, 94, 76),
+        (244, 0, 95),
+        (152, 224, 36),
+        (224, 213, 97),
+        (157, 101, 255),
+        (244, 0, 95),
+        (88, 209, 235),
+        (246, 246, 239),
+    ]),
+};
+
+const DIMMED_MONOKAI_COLORS: ThemeColors = ThemeColors {
+    background: (25, 25, 25),
+    foreground: (185, 188, 186),
+    normal: &[
+        (58, 61, 67),
+        (190, 63, 72),
+        (135, 154, 59),
+        (197, 166, 53),
+        (79, 118, 161),
+        (133, 92, 141),
+        (87, 143, 164),
+        (185, 188, 186),
+    ],
+    bright: Some(&[
+        (136, 137, 135),
+        (251, 0, 31),
+        (15, 114, 47),
+        (196, 112, 51),
+        (24, 109, 227),
+        (251, 0, 103),
+        (46, 112, 109),
+        (253, 255, 185),
+    ]),
+};
+
+const NIGHT_OWLISH_COLORS: ThemeColors = ThemeColors {
+    background: (255, 255, 255),
+    foreground: (64, 63, 83),
+    normal: &[
+        (1, 22, 39),
+        (211, 66, 62),
+        (42, 162, 152),
+        (218, 170, 1),
+        (72, 118, 214),
+        (64, 63, 83),
+        (8, 145, 106),
+        (122, 129, 129),
+    ],
+    bright: Some(&[
+        (122, 129, 129),
+        (247, 110, 110),
+        (73, 208, 197),
+        (218, 194, 107),
+        (92, 167, 228),
+        (105, 112, 152),
+        (0, 201, 144),
+        (152, 159, 177),
+    ]),
+};
+
+const DRACULA_COLORS: ThemeColors = ThemeColors {
+    background: (40, 42, 54),
+    foreground: (248, 248, 242),
+    normal: &[
+        (33, 34, 44),
+        (255, 85, 85),
+        (152, 224, 36),
+        (253, 151, 31),
+        (157, 101, 255),
+        (244, 0, 95),
+        (88, 209, 235),
+        (196, 197, 181),
+    ],
+    bright: Some(&[
+        (98, 114, 164),
+        (255, 110, 110),
+        (105, 255, 148),
+        (255, 255, 165),
+        (214, 172, 255),
+        (255, 146, 223),
+        (164, 255, 255),
+        (255, 255, 255),
+    ]),
+};
+
+const MATERIAL_OCEAN_COLORS: ThemeColors = ThemeColors {
+    background: (38, 50, 56),
+    foreground: (176, 190, 197),
+    normal: &[
         (0, 0, 0),
-        vec![
-            (0, 0, 0),
-            (128, 0, 0),
-            (0, 128, 0),
-            (128, 128, 0),
-            (0, 0, 128),
-            (128, 0, 128),
-            (0, 128, 128),
-            (192, 192, 192),
-        ],
-        Some(vec![
-            (128, 128, 128),
-            (255, 0, 0),
-            (0, 255, 0),
-            (255, 255, 0),
-            (0, 0, 255),
-            (255, 0, 255),
-            (0, 255, 255),
-            (255, 255, 255),
-        ]),
-    );
+        (220, 96, 104),
+        (171, 207, 118),
+        (230, 180, 85),
+        (110, 152, 235),
+        (180, 128, 214),
+        (113, 198, 231),
+        (238, 255, 255),
+    ],
+    bright: Some(&[
+        (0, 0, 0),
+        (240, 113, 120),
+        (195, 232, 141),
+        (255, 203, 107),
+        (130, 170, 255),
+        (199, 146, 234),
+        (137, 221, 255),
+        (238, 255, 255),
+    ]),
+};
+
+const MATERIAL_DEEP_OCEAN_COLORS: ThemeColors = ThemeColors {
+    background: (9, 11, 16),
+    foreground: (166, 172, 205),
+    normal: &[
+        (0, 0, 0),
+        (220, 96, 104),
+        (171, 207, 118),
+        (230, 180, 85),
+        (110, 152, 235),
+        (180, 128, 214),
+        (113, 198, 231),
+        (238, 255, 255),
+    ],
+    bright: Some(&[
+        (0, 0, 0),
+        (240, 113, 120),
+        (195, 232, 141),
+        (255, 203, 107),
+        (130, 170, 255),
+        (199, 146, 234),
+        (137, 221, 255),
+        (238, 255, 255),
+    ]),
+};
+
+const MATERIAL_PALENIGHT_COLORS: ThemeColors = ThemeColors {
+    background: (41, 45, 62),
+    foreground: (166, 172, 205),
+    normal: &[
+        (0, 0, 0),
+        (220, 96, 104),
+        (171, 207, 118),
+        (230, 180, 85),
+        (110, 152, 235),
+        (180, 128, 214),
+        (113, 198, 231),
+        (238, 255, 255),
+    ],
+    bright: Some(&[
+        (0, 0, 0),
+        (240, 113, 120),
+        (195, 232, 141),
+        (255, 203, 107),
+        (130, 170, 255),
+        (199, 146, 234),
+        (137, 221, 255),
+        (238, 255, 255),
+    ]),
+};
+
+const MATERIAL_LIGHTER_COLORS: ThemeColors = ThemeColors {
+    background: (250, 250, 250),
+    foreground: (84, 110, 122),
+    normal: &[
+        (0, 0, 0),
+        (220, 96, 104),
+        (171, 207, 118),
+        (230, 180, 85),
+        (110, 152, 235),
+        (180, 128, 214),
+        (113, 198, 231),
+        (255, 255, 255),
+    ],
+    bright: Some(&[
+        (0, 0, 0),
+        (229, 57, 53),
+        (145, 184, 89),
+        (246, 164, 52),
+        (97, 130, 184),
+        (124, 77, 255),
+        (57, 173, 181),
+        (255, 255, 255),
+    ]),
+};
+
+const MATERIAL_DARKER_COLORS: ThemeColors = ThemeColors {
+    background: (33, 33, 33),
+    foreground: (176, 190, 197),
+    normal: &[
+        (0, 0, 0),
+        (220, 96, 104),
+        (171, 207, 118),
+        (230, 180, 85),
+        (110, 152, 235),
+        (180, 128, 214),
+        (113, 198, 231),
+        (238, 255, 255),
+    ],
+    bright: Some(&[
+        (0, 0, 0),
+        (240, 113, 120),
+        (195, 232, 141),
+        (255, 203, 107),
+        (130, 170, 255),
+        (199, 146, 234),
+        (137, 221, 255),
+        (238, 255, 255),
+    ]),
+};
+
+const SVG_EXPORT_COLORS: ThemeColors = ThemeColors {
+    background: (41, 41, 41),
+    foreground: (197, 200, 198),
+    normal: &[
+        (75, 78, 85),
+        (204, 85, 90),
+        (152, 168, 75),
+        (208, 179, 68),
+        (96, 138, 177),
+        (152, 114, 159),
+        (104, 160, 179),
+        (197, 200, 198),
+    ],
+    bright: Some(&[
+        (154, 155, 153),
+        (255, 38, 39),
+        (0, 130, 61),
+        (208, 132, 66),
+        (25, 132, 233),
+        (255, 44, 122),
+        (57, 130, 128),
+        (253, 253, 197),
+    ]),
+};
+
+lazy_static! {
+    /// The default theme, with the standard colors of a light terminal.
+    pub static ref DEFAULT_TERMINAL_THEME: TerminalTheme = DEFAULT_COLORS.theme();
 }
 
 lazy_static! {
-    static ref MONOKAI: TerminalTheme = TerminalTheme::new(
-        (12, 12, 12),
-        (217, 217, 217),
-        vec![
-            (26, 26, 26),
-            (244, 0, 95),
-            (152, 224, 36),
-            (253, 151, 31),
-            (157, 101, 255),
-            (244, 0, 95),
-            (88, 209, 235),
-            (196, 197, 181),
-            (98, 94, 76),
-        ],
-        Some(vec![
-            (244, 0, 95),
-            (152, 224, 36),
-            (224, 213, 97),
-            (157, 101, 255),
-            (244, 0, 95),
-            (88, 209, 235),
-            (246, 246, 239),
-        ]),
-    );
+    /// The Monokai theme.
+    pub static ref MONOKAI: TerminalTheme = MONOKAI_COLORS.theme();
 }
 
 lazy_static! {
-    static ref DIMMED_MONOKAI: TerminalTheme = TerminalTheme::new(
-        (25, 25, 25),
-        (185, 188, 186),
-        vec![
-            (58, 61, 67),
-            (190, 63, 72),
-            (135, 154, 59),
-            (197, 166, 53),
-            (79, 118, 161),
-            (133, 92, 141),
-            (87, 143, 164),
-            (185, 188, 186),
-            (136, 137, 135),
-        ],
-        Some(vec![
-            (251, 0, 31),
-            (15, 114, 47),
-            (196, 112, 51),
-            (24, 109, 227),
-            (251, 0, 103),
-            (46, 112, 109),
-            (253, 255, 185),
-        ]),
-    );
+    /// A dimmer variant of the Monokai theme.
+    pub static ref DIMMED_MONOKAI: TerminalTheme = DIMMED_MONOKAI_COLORS.theme();
 }
 
 lazy_static! {
-    static ref NIGHT_OWLISH: TerminalTheme = TerminalTheme::new(
-        (255, 255, 255),
-        (64, 63, 83),
-        vec![
-            (1, 22, 39),
-            (211, 66, 62),
-            (42, 162, 152),
-            (218, 170, 1),
-            (72, 118, 214),
-            (64, 63, 83),
-            (8, 145, 106),
-            (122, 129, 129),
-            (122, 129, 129),
-        ],
-        Some(vec![
-            (247, 110, 110),
-            (73, 208, 197),
-            (218, 194, 107),
-            (92, 167, 228),
-            (105, 112, 152),
-            (0, 201, 144),
-            (152, 159, 177),
-        ]),
-    );
+    /// A light theme inspired by Night Owl.
+    pub static ref NIGHT_OWLISH: TerminalTheme = NIGHT_OWLISH_COLORS.theme();
 }
 
 lazy_static! {
-    static ref DRACULA: TerminalTheme = TerminalTheme::new(
-        (40, 42, 54),
-        (248, 248, 242),
-        vec![
-            (33, 34, 44),
-            (255, 85, 85),
-            (152, 224, 36),
-            (253, 151, 31),
-            (157, 101, 255),
-            (244, 0, 95),
-            (88, 209, 235),
-            (196, 197, 181),
-        ],
-        Some(vec![
-            (98, 114, 164),
-            (255, 110, 110),
-            (105, 255, 148),
-            (255, 255, 165),
-            (214, 172, 255),
-            (255, 146, 223),
-            (164, 255, 255),
-            (255, 255, 255),
-        ]),
-    );
+    /// The Dracula theme.
+    pub static ref DRACULA: TerminalTheme = DRACULA_COLORS.theme();
 }
 
 lazy_static! {
-    static ref MATERIAL_OCEAN: TerminalTheme = TerminalTheme::new(
-        (38, 50, 56),
-        (176, 190, 197),
-        vec![
-            (0, 0, 0),
-            (220, 96, 104),
-            (171, 207, 118),
-            (230, 180, 85),
-            (110, 152, 235),
-            (180, 128, 214),
-            (113, 198, 231),
-            (238, 255, 255),
-        ],
-        Some(vec![
-            (0, 0, 0),
-            (240, 113, 120),
-            (195, 232, 141),
-            (255, 203, 107),
-            (130, 170, 255),
-            (199, 146, 234),
-            (137, 221, 255),
-            (238, 255, 255),
-        ]),
-    );
+    /// The Ocean variant of the Material theme.
+    pub static ref MATERIAL_OCEAN: TerminalTheme = MATERIAL_OCEAN_COLORS.theme();
 }
 
 lazy_static! {
-    static ref MATERIAL_DEEP_OCEAN: TerminalTheme = TerminalTheme::new(
-        (9, 11, 16),
-        (166, 172, 205),
-        vec![
-            (0, 0, 0),
-            (220, 96, 104),
-            (171, 207, 118),
-            (230, 180, 85),
-            (110, 152, 235),
-            (180, 128, 214),
-            (113, 198, 231),
-            (238, 255, 255),
-        ],
-        Some(vec![
-            (0, 0, 0),
-            (240, 113, 120),
-            (195, 232, 141),
-            (255, 203, 107),
-            (130, 170, 255),
-            (199, 146, 234),
-            (137, 221, 255),
-            (238, 255, 255),
-        ]),
-    );
+    /// The Deep Ocean variant of the Material theme.
+    pub static ref MATERIAL_DEEP_OCEAN: TerminalTheme = MATERIAL_DEEP_OCEAN_COLORS.theme();
 }
 
 lazy_static! {
-    static ref MATERIAL_PALENIGHT: TerminalTheme = TerminalTheme::new(
-        (41, 45, 62),
-        (166, 172, 205),
-        vec![
-            (0, 0, 0),
-            (220, 96, 104),
-            (171, 207, 118),
-            (230, 180, 85),
-            (110, 152, 235),
-            (180, 128, 214),
-            (113, 198, 231),
-            (238, 255, 255),
-        ],
-        Some(vec![
-            (0, 0, 0),
-            (240, 113, 120),
-            (195, 232, 141),
-            (255, 203, 107),
-            (130, 170, 255),
-            (199, 146, 234),
-            (137, 221, 255),
-            (238, 255, 255),
-        ]),
-    );
+    /// The Palenight variant of the Material theme.
+    pub static ref MATERIAL_PALENIGHT: TerminalTheme = MATERIAL_PALENIGHT_COLORS.theme();
 }
 
 lazy_static! {
-    static ref MATERIAL_LIGHTER: TerminalTheme = TerminalTheme::new(
-        (250, 250, 250),
-        (84, 110, 122),
-        vec![
-            (0, 0, 0),
-            (220, 96, 104),
-            (171, 207, 118),
-            (230, 180, 85),
-            (110, 152, 235),
-            (180, 128, 214),
-            (113, 198, 231),
-            (255, 255, 255),
-        ],
-        Some(vec![
-            (0, 0, 0),
-            (229, 57, 53),
-            (145, 184, 89),
-            (246, 164, 52),
-            (97, 130, 184),
-            (124, 77, 255),
-            (57, 173, 181),
-            (255, 255, 255),
-        ]),
-    );
+    /// The Lighter variant of the Material theme.
+    pub static ref MATERIAL_LIGHTER: TerminalTheme = MATERIAL_LIGHTER_COLORS.theme();
 }
 
 lazy_static! {
-    static ref MATERIAL_DARKER: TerminalTheme = TerminalTheme::new(
-        (33, 33, 33),
-        (176, 190, 197),
-        vec![
-            (0, 0, 0),
-            (220, 96, 104),
-            (171, 207, 118),
-            (230, 180, 85),
-            (110, 152, 235),
-            (180, 128, 214),
-            (113, 198, 231),
-            (238, 255, 255),
-        ],
-        Some(vec![
-            (0, 0, 0),
-            (240, 113, 120),
-            (195, 232, 141),
-            (255, 203, 107),
-            (130, 170, 255),
-            (199, 146, 234),
-            (137, 221, 255),
-            (238, 255, 255),
-        ]),
-    );
+    /// The Darker variant of the Material theme.
+    pub static ref MATERIAL_DARKER: TerminalTheme = MATERIAL_DARKER_COLORS.theme();
 }
 
 lazy_static! {
-    static ref SVG_EXPORT_THEME: TerminalTheme = TerminalTheme::new(
-        (41, 41, 41),
-        (197, 200, 198),
-        vec![
-            (75, 78, 85),
-            (204, 85, 90),
-            (152, 168, 75),
-            (208, 179, 68),
-            (96, 138, 177),
-            (152, 114, 159),
-            (104, 160, 179),
-            (197, 200, 198),
-            (154, 155, 153),
-        ],
-        Some(vec![
-            (255, 38, 39),
-            (0, 130, 61),
-            (208, 132, 66),
-            (25, 132, 233),
-            (255, 44, 122),
-            (57, 130, 128),
-            (253, 253, 197),
-        ]),
-    );
+    /// The theme used by default when exporting SVG.
+    pub static ref SVG_EXPORT_THEME: TerminalTheme = SVG_EXPORT_COLORS.theme();
+}
+
+lazy_static! {
+    /// Built-in themes by name.
+    static ref THEMES: [(&'static str, &'static TerminalTheme); 11] = [
+        ("default", &DEFAULT_TERMINAL_THEME),
+        ("monokai", &MONOKAI),
+        ("dimmed_monokai", &DIMMED_MONOKAI),
+        ("night_owlish", &NIGHT_OWLISH),
+        ("dracula", &DRACULA),
+        ("material_ocean", &MATERIAL_OCEAN),
+        ("material_deep_ocean", &MATERIAL_DEEP_OCEAN),
+        ("material_palenight", &MATERIAL_PALENIGHT),
+        ("material_lighter", &MATERIAL_LIGHTER),
+        ("material_darker", &MATERIAL_DARKER),
+        ("svg_export", &SVG_EXPORT_THEME),
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("default", &DEFAULT_TERMINAL_THEME)]
+    #[case("monokai", &MONOKAI)]
+    #[case("MONOKAI", &MONOKAI)]
+    #[case(" dimmed-monokai ", &DIMMED_MONOKAI)]
+    #[case("material deep ocean", &MATERIAL_DEEP_OCEAN)]
+    #[case("svg_export", &SVG_EXPORT_THEME)]
+    fn test_by_name(#[case] name: &str, #[case] theme: &TerminalTheme) {
+        assert_eq!(TerminalTheme::by_name(name), Some(theme));
+    }
+
+    #[test]
+    fn test_by_name_unknown() {
+        assert_eq!(TerminalTheme::by_name("solarized"), None);
+        assert_eq!(TerminalTheme::by_name(""), None);
+    }
+
+    #[test]
+    fn test_all() {
+        let themes = TerminalTheme::all();
+
+        assert_eq!(themes.len(), 11);
+        for (name, theme) in themes {
+            assert_eq!(TerminalTheme::by_name(name), Some(*theme));
+        }
+    }
+
+    #[test]
+    fn test_ansi_colors() {
+        assert_eq!(
+            DEFAULT_TERMINAL_THEME.ansi_colors()[1],
+            ColorTriplet::new(128, 0, 0)
+        );
+        assert_eq!(
+            DEFAULT_TERMINAL_THEME.ansi_colors()[9],
+            ColorTriplet::new(255, 0, 0)
+        );
+    }
+
+    #[rstest]
+    #[case(7, None, Err(TerminalThemeError::NormalColorCount(7)))]
+    #[case(9, Some(8), Err(TerminalThemeError::NormalColorCount(9)))]
+    #[case(8, Some(7), Err(TerminalThemeError::BrightColorCount(7)))]
+    #[case(8, Some(0), Err(TerminalThemeError::BrightColorCount(0)))]
+    #[case(8, Some(8), Ok(()))]
+    #[case(8, None, Ok(()))]
+    fn test_try_new(
+        #[case] normal: usize,
+        #[case] bright: Option<usize>,
+        #[case] result: Result<(), TerminalThemeError>,
+    ) {
+        let theme = TerminalTheme::try_new(
+            (0, 0, 0),
+            (255, 255, 255),
+            vec![(1, 1, 1); normal],
+            bright.map(|bright| vec![(2, 2, 2); bright]),
+        );
+
+        assert_eq!(theme.map(|_| ()), result);
+    }
+
+    #[test]
+    fn test_try_new_without_bright() {
+        let normal: Vec<_> = (0..8).map(|index| (index, index, index)).collect();
+        let theme = TerminalTheme::try_new((0, 0, 0), (255, 255, 255), normal, None).unwrap();
+
+        assert_eq!(theme.ansi_colors()[..8], theme.ansi_colors()[8..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 8 normal intensity colors, got 9")]
+    fn test_new_panics() { TerminalTheme::new((0, 0, 0), (0, 0, 0), vec![(0, 0, 0); 9], None); }
+
+    #[test]
+    fn test_builtin_themes_construct() {
+        let themes = [
+            ("default", &DEFAULT_COLORS),
+            ("monokai", &MONOKAI_COLORS),
+            ("dimmed_monokai", &DIMMED_MONOKAI_COLORS),
+            ("night_owlish", &NIGHT_OWLISH_COLORS),
+            ("dracula", &DRACULA_COLORS),
+            ("material_ocean", &MATERIAL_OCEAN_COLORS),
+            ("material_deep_ocean", &MATERIAL_DEEP_OCEAN_COLORS),
+            ("material_palenight", &MATERIAL_PALENIGHT_COLORS),
+            ("material_lighter", &MATERIAL_LIGHTER_COLORS),
+            ("material_darker", &MATERIAL_DARKER_COLORS),
+            ("svg_export", &SVG_EXPORT_COLORS),
+        ];
+        assert_eq!(themes.len(), TerminalTheme::all().len());
+
+        for (name, colors) in themes {
+            let theme = TerminalTheme::try_new(
+                colors.background,
+                colors.foreground,
+                colors.normal.to_vec(),
+                colors.bright.map(<[_]>::to_vec),
+            );
+
+            assert_eq!(
+                theme.as_ref(),
+                Ok(TerminalTheme::by_name(name).unwrap()),
+                "{name}"
+            );
+        }
+
+        // Bright black used to be listed as a ninth normal intensity color.
+        assert_eq!(MONOKAI.ansi_colors()[7], ColorTriplet::new(196, 197, 181));
+        assert_eq!(MONOKAI.ansi_colors()[8], ColorTriplet::new(98, 94, 76));
+        assert_eq!(
+            SVG_EXPORT_THEME.ansi_colors()[8],
+            ColorTriplet::new(154, 155, 153)
+        );
+    }
+
+    #[rstest]
+    #[case(ColorTriplet::new(0, 0, 0), ColorTriplet::new(255, 255, 255))]
+    #[case(ColorTriplet::new(255, 255, 255), ColorTriplet::new(0, 0, 0))]
+    #[case(ColorTriplet::new(0, 128, 255), ColorTriplet::new(0, 0, 0))]
+    #[case(ColorTriplet::new(128, 0, 0), ColorTriplet::new(255, 255, 255))]
+    fn test_readable_foreground(#[case] background: ColorTriplet, #[case] result: ColorTriplet) {
+        assert_eq!(
+            DEFAULT_TERMINAL_THEME.readable_foreground(background),
+            result
+        );
+    }
+
+    #[test]
+    fn test_readable_foreground_fallback() {
+        // Both theme colors are a mid grey, which is only readable on dark
+        // backgrounds.
+        let theme = TerminalTheme::new((110, 110, 110), (130, 130, 130), vec![(0, 0, 0); 8], None);
+
+        assert_eq!(
+            theme.readable_foreground(ColorTriplet::new(120, 120, 120)),
+            ColorTriplet::new(0, 0, 0)
+        );
+        assert_eq!(
+            theme.readable_foreground(ColorTriplet::new(20, 20, 20)),
+            ColorTriplet::new(130, 130, 130)
+        );
+        assert_eq!(
+            theme.readable_foreground(ColorTriplet::new(200, 200, 200)),
+            ColorTriplet::new(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_with_colors() {
+        let theme = DEFAULT_TERMINAL_THEME
+            .clone()
+            .with_cursor_color(ColorTriplet::new(1, 2, 3))
+            .with_selection_colors(ColorTriplet::new(4, 5, 6), None)
+            .with_bold_is_bright(true);
+
+        assert_eq!(theme.cursor_color, Some(ColorTriplet::new(1, 2, 3)));
+        assert_eq!(theme.selection_background, Some(ColorTriplet::new(4, 5, 6)));
+        assert_eq!(theme.selection_foreground, None);
+        assert!(theme.bold_is_bright);
+        assert_eq!(theme.ansi_colors(), DEFAULT_TERMINAL_THEME.ansi_colors());
+    }
+
+    #[test]
+    fn test_terminal_theme_error_display() {
+        assert_eq!(
+            TerminalThemeError::BrightColorCount(7).to_string(),
+            "expected 8 bright colors, got 7"
+        );
+    }
 }
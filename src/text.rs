@@ -0,0 +1,1291 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+use regex::Regex;
+
+use crate::cells::{cell_len, set_cell_size};
+use crate::color::Color;
+use crate::console::{Console, ConsoleOptions, JustifyMethod, OverflowMethod, Renderable};
+use crate::gradient::Gradient;
+use crate::markup::{self, MarkupError};
+use crate::measure::Measurement;
+use crate::segment::Segment;
+use crate::style::Style;
+use crate::wrap::divide_line;
+
+/// Number of cells a tab character is expanded to.
+const DEFAULT_TAB_SIZE: u32 = 8;
+
+/// A marked up region in some text.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Span {
+    /// Span start byte offset.
+    pub start: usize,
+    /// Span end byte offset.
+    pub end: usize,
+    /// Style associated with the span.
+    pub style: Style,
+}
+
+impl Span {
+    /// Instantiate a new [`Span`].
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Span start byte offset.
+    /// * `end` - Span end byte offset.
+    /// * `style` - Style associated with the span.
+    pub const fn new(start: usize, end: usize, style: Style) -> Self { Self { start, end, style } }
+
+    /// Check if the span covers no text.
+    pub const fn is_empty(&self) -> bool { self.end <= self.start }
+}
+
+/// Text with color / style.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Text {
+    plain: String,
+    /// Marked up regions of the text.
+    pub spans: Vec<Span>,
+    /// Base style for the text.
+    pub style: Style,
+    /// Justify method, or `None` to use the console's default.
+    pub justify: Option<JustifyMethod>,
+    /// Overflow method, or `None` to use the console's default.
+    pub overflow: Option<OverflowMethod>,
+    /// Disable text wrapping, or `None` to use the console's default.
+    pub no_wrap: Option<bool>,
+    /// Character to end text with.
+    pub end: String,
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Self {
+            plain: String::new(),
+            spans: vec![],
+            style: Style::default(),
+            justify: None,
+            overflow: None,
+            no_wrap: None,
+            end: "\n".into(),
+        }
+    }
+}
+
+impl Text {
+    /// Instantiate a new [`Text`].
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Unstyled text.
+    /// * `style` - Base style for the text.
+    pub fn new(text: impl Into<String>, style: Style) -> Self {
+        Self {
+            plain: text.into(),
+            style,
+            ..Self::default()
+        }
+    }
+
+    /// Create [`Text`] instance from markup.
+    ///
+    /// # Arguments
+    ///
+    /// * `markup` - A string containing console markup.
+    ///
+    /// # Errors
+    ///
+    /// [`MarkupError`] if there is a syntax error in the markup.
+    pub fn from_markup(markup: &str) -> Result<Self, MarkupError> { markup::render(markup) }
+
+    /// Get the text as a single string.
+    pub fn plain(&self) -> &str { &self.plain }
+
+    /// Get the length of the text in bytes.
+    pub fn len(&self) -> usize { self.plain.len() }
+
+    /// Check if the text is empty.
+    pub fn is_empty(&self) -> bool { self.plain.is_empty() }
+
+    /// Clamp a byte offset to the length of the text, moving it back to the
+    /// start of the character it falls inside.
+    fn snap_offset(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.len());
+        while !self.plain.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        offset
+    }
+
+    /// Get the number of cells required to render this text.
+    pub fn cell_len(&self) -> u32 { cell_len(&self.plain) }
+
+    /// Add text with an optional style.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to append.
+    /// * `style` - A style to apply to the appended text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::style::Style;
+    /// use wealthy::text::{Span, Text};
+    ///
+    /// let mut text = Text::new("foo", Style::default());
+    /// text.append("bar", Some(Style::parse("bold").unwrap()));
+    ///
+    /// assert_eq!(text.plain(), "foobar");
+    /// assert_eq!(text.spans, [Span::new(3, 6, Style::parse("bold").unwrap())]);
+    /// ```
+    pub fn append(&mut self, text: &str, style: Option<Style>) -> &mut Self {
+        if !text.is_empty() {
+            let offset = self.len();
+            self.plain.push_str(text);
+
+            if let Some(style) = style {
+                self.spans.push(Span::new(offset, self.len(), style));
+            }
+        }
+
+        self
+    }
+
+    /// Append another [`Text`] instance. This method is more performant than
+    /// [`Text::append`], but only works for [`Text`].
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to append.
+    pub fn append_text(&mut self, text: &Self) -> &mut Self {
+        let offset = self.len();
+
+        if !text.style.is_null() {
+            self.spans
+                .push(Span::new(offset, offset + text.len(), text.style.clone()));
+        }
+        self.plain.push_str(&text.plain);
+        self.spans.extend(
+            text.spans
+                .iter()
+                .map(|span| Span::new(span.start + offset, span.end + offset, span.style.clone())),
+        );
+
+        self
+    }
+
+    /// Replace the text, removing or cropping any spans past its new end.
+    ///
+    /// # Arguments
+    ///
+    /// * `plain` - The new text.
+    pub fn set_plain(&mut self, plain: impl Into<String>) {
+        let plain = plain.into();
+
+        if plain.len() < self.len() {
+            self.trim_spans(plain.len());
+        }
+        self.plain = plain;
+    }
+
+    /// Remove or crop spans so that they end before `max_offset`.
+    fn trim_spans(&mut self, max_offset: usize) {
+        self.spans.retain(|span| span.start < max_offset);
+        for span in &mut self.spans {
+            span.end = span.end.min(max_offset);
+        }
+    }
+
+    /// Get a copy of this instance with different text and no spans.
+    fn blank_copy(&self, plain: &str) -> Self {
+        Self {
+            plain: plain.into(),
+            spans: vec![],
+            style: self.style.clone(),
+            justify: self.justify,
+            overflow: self.overflow,
+            no_wrap: self.no_wrap,
+            end: self.end.clone(),
+        }
+    }
+
+    /// Apply a style to a portion of the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - Style to apply.
+    /// * `start` - Start byte offset.
+    /// * `end` - End byte offset, or `None` for the end of the text.
+    ///
+    /// Offsets past the end of the text are clamped to its length, and offsets
+    /// inside a character are moved back to the start of that character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::style::Style;
+    /// use wealthy::text::{Span, Text};
+    ///
+    /// let mut text = Text::from("foobar");
+    /// text.stylize(Style::parse("bold").unwrap(), 3, None);
+    ///
+    /// assert_eq!(text.spans, [Span::new(3, 6, Style::parse("bold").unwrap())]);
+    /// ```
+    pub fn stylize(&mut self, style: Style, start: usize, end: Option<usize>) {
+        let start = self.snap_offset(start);
+        let end = self.snap_offset(end.unwrap_or(self.len()));
+
+        if start < end {
+            self.spans.push(Span::new(start, end, style));
+        }
+    }
+
+    /// Highlight text matching a regular expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `re_highlight` - A regular expression.
+    /// * `style` - Style to apply to the matches.
+    ///
+    /// # Returns
+    ///
+    /// Number of regex matches.
+    pub fn highlight_regex(&mut self, re_highlight: &Regex, style: &Style) -> usize {
+        let matches: Vec<_> = re_highlight
+            .find_iter(&self.plain)
+            .map(|found| (found.start(), found.end()))
+            .collect();
+
+        for &(start, end) in &matches {
+            self.stylize(style.clone(), start, Some(end));
+        }
+
+        matches.len()
+    }
+
+    /// Color the characters of the text along a gradient, from the first stop
+    /// at the first character to the last stop at the last character.
+    ///
+    /// Neighbouring characters with the same color share a span.
+    ///
+    /// # Arguments
+    ///
+    /// * `gradient` - The gradient to take the colors from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::color::Color;
+    /// use wealthy::color_triplet::ColorTriplet;
+    /// use wealthy::gradient::Gradient;
+    /// use wealthy::text::Text;
+    ///
+    /// let mut text = Text::from("abc");
+    /// text.apply_gradient(&Gradient::new([
+    ///     ColorTriplet::new(0, 0, 0),
+    ///     ColorTriplet::new(200, 0, 0),
+    /// ]));
+    ///
+    /// assert_eq!(text.spans.len(), 3);
+    /// assert_eq!(text.spans[1].style.color, Some(Color::from_rgb(100, 0, 0)));
+    /// ```
+    pub fn apply_gradient(&mut self, gradient: &Gradient) {
+        let offsets: Vec<_> = self
+            .plain
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .collect();
+        let colors = gradient.colors(offsets.len());
+        let mut start = 0;
+
+        for (index, color) in colors.iter().enumerate() {
+            if colors.get(index + 1) != Some(color) {
+                let end = offsets.get(index + 1).copied();
+                let style = Style {
+                    color: Some(Color::from_triplet(*color)),
+                    ..Style::default()
+                };
+
+                self.stylize(style, offsets[start], end);
+                start = index + 1;
+            }
+        }
+    }
+
+    /// Get the style of a character at a given byte offset.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Byte offset into the text.
+    pub fn get_style_at_offset(&self, offset: usize) -> Style {
+        self.spans
+            .iter()
+            .filter(|span| span.start <= offset && offset < span.end)
+            .fold(self.style.clone(), |style, span| &style + &span.style)
+    }
+
+    /// Join text together with this instance as the separator.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The texts to join.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::text::Text;
+    ///
+    /// let text = Text::from(", ").join(&[Text::from("foo"), Text::from("bar")]);
+    ///
+    /// assert_eq!(text.plain(), "foo, bar");
+    /// ```
+    pub fn join(&self, lines: &[Self]) -> Self {
+        let mut new_text = self.blank_copy("");
+
+        for (index, line) in lines.iter().enumerate() {
+            if index > 0 && !self.is_empty() {
+                new_text.append_text(self);
+            }
+            new_text.append_text(line);
+        }
+
+        new_text
+    }
+
+    /// Divide the text into several lines at the given offsets.
+    ///
+    /// # Arguments
+    ///
+    /// * `offsets` - Byte offsets to divide the text at, in ascending order.
+    ///
+    /// Offsets past the end of the text are clamped to its length, and offsets
+    /// inside a character are moved back to the start of that character. An
+    /// offset lower than the one before it gives an empty line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::text::Text;
+    ///
+    /// let lines = Text::from("foobarbaz").divide(&[3, 6]);
+    ///
+    /// assert_eq!(
+    ///     lines,
+    ///     [Text::from("foo"), Text::from("bar"), Text::from("baz")]
+    /// );
+    /// ```
+    pub fn divide(&self, offsets: &[usize]) -> Vec<Self> {
+        let mut previous = 0;
+        let divide_offsets: Vec<usize> = [0]
+            .into_iter()
+            .chain(offsets.iter().map(|&offset| {
+                previous = self.snap_offset(offset).max(previous);
+                previous
+            }))
+            .chain([self.len()])
+            .collect();
+
+        divide_offsets
+            .windows(2)
+            .map(|range| {
+                let (start, end) = (range[0], range[1]);
+                let mut line = self.blank_copy(&self.plain[start..end]);
+
+                line.spans = self
+                    .spans
+                    .iter()
+                    .filter_map(|span| {
+                        let new_start = span.start.max(start) - start;
+                        let new_end = span.end.min(end).saturating_sub(start);
+
+                        (new_end > new_start)
+                            .then(|| Span::new(new_start, new_end, span.style.clone()))
+                    })
+                    .collect();
+
+                line
+            })
+            .collect()
+    }
+
+    /// Split the text into lines on a separator.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - String to split on.
+    /// * `include_separator` - Include the separator at the end of the lines.
+    /// * `allow_blank` - Return a blank line if the text ends with a separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::text::Text;
+    ///
+    /// let lines = Text::from("foo\nbar\n").split("\n", false, false);
+    ///
+    /// assert_eq!(lines, [Text::from("foo"), Text::from("bar")]);
+    /// ```
+    pub fn split(&self, separator: &str, include_separator: bool, allow_blank: bool) -> Vec<Self> {
+        if separator.is_empty() || !self.plain.contains(separator) {
+            return vec![self.clone()];
+        }
+
+        let matches = self
+            .plain
+            .match_indices(separator)
+            .map(|(offset, _)| offset);
+        let mut lines = if include_separator {
+            let offsets: Vec<usize> = matches.map(|offset| offset + separator.len()).collect();
+
+            self.divide(&offsets)
+        } else {
+            let offsets: Vec<usize> = matches
+                .flat_map(|offset| [offset, offset + separator.len()])
+                .collect();
+
+            self.divide(&offsets)
+                .into_iter()
+                .filter(|line| line.plain != separator)
+                .collect()
+        };
+
+        if !allow_blank && self.plain.ends_with(separator) {
+            lines.pop();
+        }
+
+        lines
+    }
+
+    /// Truncate the text to fit within a given number of cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_width` - Maximum number of cells.
+    /// * `overflow` - Overflow method, or `None` to use the text's own.
+    /// * `pad` - Pad the text with spaces if it is shorter than `max_width`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::console::OverflowMethod;
+    /// use wealthy::text::Text;
+    ///
+    /// let mut text = Text::from("foobarbaz");
+    /// text.truncate(6, Some(OverflowMethod::Ellipsis), false);
+    ///
+    /// assert_eq!(text.plain(), "fooba…");
+    /// ```
+    pub fn truncate(&mut self, max_width: u32, overflow: Option<OverflowMethod>, pad: bool) {
+        let overflow = overflow.or(self.overflow).unwrap_or_default();
+
+        if overflow == OverflowMethod::Ignore {
+            return;
+        }
+
+        let length = self.cell_len();
+        if length > max_width {
+            let plain = match overflow {
+                OverflowMethod::Ellipsis => {
+                    set_cell_size(self.plain.clone(), max_width.saturating_sub(1)) + "…"
+                },
+                _ => set_cell_size(self.plain.clone(), max_width),
+            };
+            self.set_plain(plain);
+        }
+        if pad && length < max_width {
+            self.pad_right(max_width - length, ' ');
+        }
+    }
+
+    /// Pad both sides of the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of characters to add to each side.
+    /// * `character` - Character to pad with.
+    pub fn pad(&mut self, count: u32, character: char) {
+        self.pad_left(count, character);
+        self.pad_right(count, character);
+    }
+
+    /// Pad the left of the text, moving the spans along with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of characters to add.
+    /// * `character` - Character to pad with.
+    pub fn pad_left(&mut self, count: u32, character: char) {
+        let padding = character.to_string().repeat(count as usize);
+
+        for span in &mut self.spans {
+            span.start += padding.len();
+            span.end += padding.len();
+        }
+        self.plain.insert_str(0, &padding);
+    }
+
+    /// Pad the right of the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of characters to add.
+    /// * `character` - Character to pad with.
+    pub fn pad_right(&mut self, count: u32, character: char) {
+        self.plain
+            .extend(std::iter::repeat_n(character, count as usize));
+    }
+
+    /// Remove a number of characters from the end of the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of characters to remove.
+    pub fn right_crop(&mut self, amount: usize) {
+        if amount == 0 {
+            return;
+        }
+
+        let max_offset = self
+            .plain
+            .char_indices()
+            .rev()
+            .nth(amount - 1)
+            .map_or(0, |(offset, _)| offset);
+
+        self.trim_spans(max_offset);
+        self.plain.truncate(max_offset);
+    }
+
+    /// Strip whitespace from the end of the text.
+    pub fn rstrip(&mut self) {
+        let length = self.plain.trim_end().len();
+
+        self.trim_spans(length);
+        self.plain.truncate(length);
+    }
+
+    /// Remove whitespace beyond a certain width at the end of the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The desired size of the text in cells.
+    pub fn rstrip_end(&mut self, size: u32) {
+        let text_length = self.cell_len();
+
+        if text_length > size {
+            let excess = (text_length - size) as usize;
+            let whitespace_count = self.plain[self.plain.trim_end().len()..].chars().count();
+
+            self.right_crop(whitespace_count.min(excess));
+        }
+    }
+
+    /// Add spaces to the end of the text, extending any spans that reach the
+    /// end of it.
+    fn extend_style(&mut self, spaces: u32) {
+        let end_offset = self.len();
+
+        for span in &mut self.spans {
+            if span.end >= end_offset {
+                span.end += spaces as usize;
+            }
+        }
+        self.pad_right(spaces, ' ');
+    }
+
+    /// Replace tabs with spaces, aligning to multiples of `tab_size` cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_size` - Number of cells per tab stop.
+    pub fn expand_tabs(&mut self, tab_size: u32) {
+        if !self.plain.contains('\t') {
+            return;
+        }
+
+        let mut new_lines = vec![];
+        for line in self.split("\n", true, false) {
+            if !line.plain.contains('\t') {
+                new_lines.push(line);
+                continue;
+            }
+
+            let mut cell_position = 0;
+            for mut part in line.split("\t", true, false) {
+                if part.plain.ends_with('\t') {
+                    part.plain.pop();
+                    part.plain.push(' ');
+                    cell_position += part.cell_len();
+
+                    let tab_remainder = cell_position % tab_size.max(1);
+                    if tab_remainder > 0 {
+                        let spaces = tab_size - tab_remainder;
+                        part.extend_style(spaces);
+                        cell_position += spaces;
+                    }
+                } else {
+                    cell_position += part.cell_len();
+                }
+                new_lines.push(part);
+            }
+        }
+
+        let result = Self::default().join(&new_lines);
+        self.plain = result.plain;
+        self.spans = result.spans;
+    }
+
+    /// Word wrap the text into lines that fit within `width` cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Number of cells available per line.
+    /// * `justify` - Justify method, or `None` to use the text's own.
+    /// * `overflow` - Overflow method, or `None` to use the text's own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wealthy::console::JustifyMethod;
+    /// use wealthy::text::Text;
+    ///
+    /// let lines = Text::from("foo bar baz").wrap(7, Some(JustifyMethod::Right), None);
+    /// let lines: Vec<&str> = lines.iter().map(Text::plain).collect();
+    ///
+    /// assert_eq!(lines, ["foo bar", "    baz"]);
+    /// ```
+    pub fn wrap(
+        &self,
+        width: u32,
+        justify: Option<JustifyMethod>,
+        overflow: Option<OverflowMethod>,
+    ) -> Vec<Self> {
+        self.wrap_lines(width, justify, overflow, self.no_wrap.unwrap_or(false))
+    }
+
+    fn wrap_lines(
+        &self,
+        width: u32,
+        justify: Option<JustifyMethod>,
+        overflow: Option<OverflowMethod>,
+        no_wrap: bool,
+    ) -> Vec<Self> {
+        let justify = justify.or(self.justify).unwrap_or_default();
+        let overflow = overflow.or(self.overflow).unwrap_or_default();
+        let no_wrap = no_wrap || overflow == OverflowMethod::Ignore;
+
+        let mut lines = vec![];
+        for mut line in self.split("\n", false, true) {
+            line.expand_tabs(DEFAULT_TAB_SIZE);
+
+            let mut new_lines = if no_wrap {
+                vec![line]
+            } else {
+                let offsets = divide_line(&line.plain, width, overflow == OverflowMethod::Fold);
+                line.divide(&offsets)
+            };
+            for line in &mut new_lines {
+                line.rstrip_end(width);
+            }
+            justify_lines(&mut new_lines, width, justify, overflow);
+            for line in &mut new_lines {
+                line.truncate(width, Some(overflow), false);
+            }
+
+            lines.extend(new_lines);
+        }
+
+        lines
+    }
+
+    /// Render the text as segments, applying the styles of the spans.
+    ///
+    /// Span offsets are clamped and moved back to character boundaries, as in
+    /// [`Text::stylize`]. Spans that are then empty, or that start after they
+    /// end, are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `end` - Optional string to add after the text.
+    pub fn render_segments(&self, end: &str) -> Vec<Segment> {
+        let mut segments = vec![];
+        let as_segment_style = |style: Style| (!style.is_null()).then_some(style);
+
+        if self.spans.is_empty() {
+            if !self.plain.is_empty() {
+                segments.push(Segment::new(
+                    self.plain.clone(),
+                    as_segment_style(self.style.clone()),
+                ));
+            }
+        } else {
+            // Offsets where styles start or end, along with whether a style is being
+            // left, and the style's index (0 is the base style).
+            let mut boundaries = vec![(0, false, 0), (self.len(), true, 0)];
+            for (index, span) in self.spans.iter().enumerate() {
+                let (start, end) = (self.snap_offset(span.start), self.snap_offset(span.end));
+                if start < end {
+                    boundaries.push((start, false, index + 1));
+                    boundaries.push((end, true, index + 1));
+                }
+            }
+            boundaries.sort_by_key(|&(offset, leaving, _)| (offset, leaving));
+
+            let mut stack = HashSet::new();
+            for (&(offset, leaving, style_id), &(next_offset, ..)) in
+                boundaries.iter().zip(boundaries.iter().skip(1))
+            {
+                if leaving {
+                    stack.remove(&style_id);
+                } else {
+                    stack.insert(style_id);
+                }
+
+                if next_offset > offset {
+                    let mut style_ids: Vec<&usize> = stack.iter().collect();
+                    style_ids.sort_unstable();
+
+                    let style = style_ids
+                        .into_iter()
+                        .map(|&style_id| match style_id {
+                            0 => &self.style,
+                            _ => &self.spans[style_id - 1].style,
+                        })
+                        .fold(Style::default(), |combined, style| &combined + style);
+
+                    segments.push(Segment::new(
+                        &self.plain[offset..next_offset],
+                        as_segment_style(style),
+                    ));
+                }
+            }
+        }
+
+        if !end.is_empty() {
+            segments.push(Segment::new(end, None));
+        }
+
+        segments
+    }
+}
+
+/// Justify lines of text to fit within `width` cells.
+fn justify_lines(lines: &mut [Text], width: u32, justify: JustifyMethod, overflow: OverflowMethod) {
+    match justify {
+        JustifyMethod::Default => {},
+        JustifyMethod::Left => {
+            for line in lines {
+                line.truncate(width, Some(overflow), true);
+            }
+        },
+        JustifyMethod::Center => {
+            for line in lines {
+                line.rstrip();
+                line.truncate(width, Some(overflow), false);
+                line.pad_left(width.saturating_sub(line.cell_len()) / 2, ' ');
+                line.pad_right(width.saturating_sub(line.cell_len()), ' ');
+            }
+        },
+        JustifyMethod::Right => {
+            for line in lines {
+                line.rstrip();
+                line.truncate(width, Some(overflow), false);
+                line.pad_left(width.saturating_sub(line.cell_len()), ' ');
+            }
+        },
+        JustifyMethod::Full => {
+            let last = lines.len().saturating_sub(1);
+
+            for line in &mut lines[..last] {
+                let words = line.split(" ", false, false);
+                let words_size: u32 = words.iter().map(Text::cell_len).sum();
+                let mut spaces = vec![1; words.len().saturating_sub(1)];
+
+                // Distribute extra spaces from right to left.
+                let mut num_spaces: u32 = spaces.iter().sum();
+                let mut index = 0;
+                while !spaces.is_empty() && words_size + num_spaces < width {
+                    let space_index = spaces.len() - index - 1;
+                    spaces[space_index] += 1;
+                    num_spaces += 1;
+                    index = (index + 1) % spaces.len();
+                }
+
+                let mut tokens = vec![];
+                for (index, word) in words.iter().enumerate() {
+                    tokens.push(word.clone());
+
+                    if let Some(&count) = spaces.get(index) {
+                        let last_offset = word
+                            .plain
+                            .char_indices()
+                            .last()
+                            .map_or(0, |(offset, _)| offset);
+                        let style = word.get_style_at_offset(last_offset);
+                        let next_style = words
+                            .get(index + 1)
+                            .map_or_else(|| line.style.clone(), |word| word.get_style_at_offset(0));
+                        let space_style = if style == next_style {
+                            style
+                        } else {
+                            line.style.clone()
+                        };
+
+                        tokens.push(Text::new(" ".repeat(count as usize), space_style));
+                    }
+                }
+
+                *line = Text::from("").join(&tokens);
+            }
+        },
+    }
+}
+
+impl From<&str> for Text {
+    fn from(text: &str) -> Self { Self::new(text, Style::default()) }
+}
+
+impl From<String> for Text {
+    fn from(text: String) -> Self { Self::new(text, Style::default()) }
+}
+
+impl Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.plain) }
+}
+
+impl Renderable for Text {
+    fn render(&self, _console: &Console<'_>, options: &ConsoleOptions) -> Vec<Segment> {
+        let lines = self.wrap_lines(
+            options.max_width,
+            self.justify.or(options.justify),
+            self.overflow.or(options.overflow),
+            self.no_wrap.unwrap_or(options.no_wrap),
+        );
+
+        Self::from("\n").join(&lines).render_segments(&self.end)
+    }
+
+    fn measure(&self, console: &Console<'_>, options: &ConsoleOptions) -> Option<Measurement> {
+        self.plain.measure(console, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::color_triplet::ColorTriplet;
+
+    fn style(style_definition: &str) -> Style { Style::parse(style_definition).unwrap() }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Text::default(), Text::new("", Style::default()));
+        assert_eq!(Text::default().end, "\n");
+    }
+
+    #[test]
+    fn test_append() {
+        let mut text = Text::from("foo");
+        text.append("bar", Some(style("bold")))
+            .append("", Some(style("red")))
+            .append("baz", None);
+
+        assert_eq!(text.plain(), "foobarbaz");
+        assert_eq!(text.spans, [Span::new(3, 6, style("bold"))]);
+    }
+
+    #[test]
+    fn test_append_text() {
+        let mut text = Text::from("foo");
+        let mut other = Text::new("bar", style("bold"));
+        other.spans.push(Span::new(1, 2, style("red")));
+        text.append_text(&other);
+
+        assert_eq!(text.plain(), "foobar");
+        assert_eq!(
+            text.spans,
+            [
+                Span::new(3, 6, style("bold")),
+                Span::new(4, 5, style("red"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_segments() {
+        let mut text = Text::new("foobarbaz", style("italic"));
+        text.spans.push(Span::new(0, 6, style("bold")));
+        text.spans.push(Span::new(3, 9, style("not bold red")));
+
+        assert_eq!(
+            text.render_segments("\n"),
+            [
+                Segment::new("foo", Some(style("bold italic"))),
+                Segment::new("bar", Some(style("not bold italic red"))),
+                Segment::new("baz", Some(style("not bold italic red"))),
+                Segment::line(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_segments_unstyled() {
+        assert_eq!(
+            Text::from("foo").render_segments(""),
+            [Segment::new("foo", None)]
+        );
+        assert_eq!(Text::from("").render_segments(""), []);
+    }
+
+    #[test]
+    fn test_render_segments_char_boundary() {
+        let mut text = Text::from("愛x");
+        text.spans.push(Span::new(1, 100, style("bold")));
+
+        assert_eq!(
+            text.render_segments(""),
+            [Segment::new("愛x", Some(style("bold")))]
+        );
+    }
+
+    #[test]
+    fn test_render_segments_inverted_span() {
+        let mut text = Text::from("foobar");
+        text.spans.push(Span::new(4, 2, style("bold")));
+        text.spans.push(Span::new(3, 3, style("italic")));
+
+        assert_eq!(text.render_segments(""), [Segment::new("foobar", None)]);
+    }
+
+    #[test]
+    fn test_from_markup() {
+        let text = Text::from_markup("[bold]foo[/] bar").unwrap();
+
+        assert_eq!(text.plain(), "foo bar");
+        assert_eq!(text.spans, [Span::new(0, 3, style("bold"))]);
+        assert!(Text::from_markup("foo[/]").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Text::from("foo").to_string(), "foo");
+    }
+
+    #[test]
+    fn test_set_plain() {
+        let mut text = Text::from("foobarbaz");
+        text.stylize(style("bold"), 0, Some(3));
+        text.stylize(style("red"), 3, Some(9));
+        text.set_plain("foob");
+
+        assert_eq!(text.plain(), "foob");
+        assert_eq!(
+            text.spans,
+            [
+                Span::new(0, 3, style("bold")),
+                Span::new(3, 4, style("red"))
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(0, None, vec![Span::new(0, 6, style("bold"))])]
+    #[case(2, Some(4), vec![Span::new(2, 4, style("bold"))])]
+    #[case(2, Some(100), vec![Span::new(2, 6, style("bold"))])]
+    #[case(4, Some(2), vec![])]
+    #[case(6, None, vec![])]
+    fn test_stylize(#[case] start: usize, #[case] end: Option<usize>, #[case] spans: Vec<Span>) {
+        let mut text = Text::from("foobar");
+        text.stylize(style("bold"), start, end);
+
+        assert_eq!(text.spans, spans);
+    }
+
+    #[rstest]
+    #[case(1, None, vec![Span::new(0, 4, style("bold"))])]
+    #[case(1, Some(2), vec![])]
+    #[case(0, Some(2), vec![])]
+    #[case(4, Some(100), vec![])]
+    fn test_stylize_char_boundary(
+        #[case] start: usize,
+        #[case] end: Option<usize>,
+        #[case] spans: Vec<Span>,
+    ) {
+        let mut text = Text::from("愛x");
+        text.stylize(style("bold"), start, end);
+
+        assert_eq!(text.spans, spans);
+    }
+
+    #[test]
+    fn test_apply_gradient() {
+        let mut text = Text::from("a愛bc");
+        text.apply_gradient(&Gradient::new([
+            ColorTriplet::new(0, 0, 0),
+            ColorTriplet::new(0, 0, 0),
+            ColorTriplet::new(0, 0, 0),
+            ColorTriplet::new(255, 0, 0),
+        ]));
+
+        assert_eq!(
+            text.spans,
+            [
+                Span::new(0, 5, style("#000000")),
+                Span::new(5, 6, style("#ff0000")),
+            ]
+        );
+
+        let mut text = Text::from("");
+        text.apply_gradient(&Gradient::new([ColorTriplet::new(0, 0, 0)]));
+
+        assert!(text.spans.is_empty());
+    }
+
+    #[test]
+    fn test_highlight_regex() {
+        let mut text = Text::from("peek-a-boo");
+        let count = text.highlight_regex(&Regex::new("[aeiou]+").unwrap(), &style("red"));
+
+        assert_eq!(count, 3);
+        assert_eq!(
+            text.spans,
+            [
+                Span::new(1, 3, style("red")),
+                Span::new(5, 6, style("red")),
+                Span::new(8, 10, style("red")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_style_at_offset() {
+        let mut text = Text::new("foobar", style("italic"));
+        text.stylize(style("bold"), 3, None);
+
+        assert_eq!(text.get_style_at_offset(0), style("italic"));
+        assert_eq!(text.get_style_at_offset(4), style("bold italic"));
+    }
+
+    #[test]
+    fn test_join() {
+        let text = Text::new(", ", style("red"))
+            .join(&[Text::new("foo", style("bold")), Text::from("bar")]);
+
+        assert_eq!(text.plain(), "foo, bar");
+        assert_eq!(
+            text.spans,
+            [
+                Span::new(0, 3, style("bold")),
+                Span::new(3, 5, style("red"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_divide() {
+        let mut text = Text::from("foo bar baz");
+        text.stylize(style("bold"), 2, Some(5));
+        let lines = text.divide(&[4, 8]);
+
+        assert_eq!(
+            lines.iter().map(Text::plain).collect::<Vec<_>>(),
+            ["foo ", "bar ", "baz"]
+        );
+        assert_eq!(lines[0].spans, [Span::new(2, 4, style("bold"))]);
+        assert_eq!(lines[1].spans, [Span::new(0, 1, style("bold"))]);
+        assert_eq!(lines[2].spans, []);
+        assert_eq!(text.divide(&[]), [text]);
+    }
+
+    #[rstest]
+    #[case(&[1], &["", "愛x"])]
+    #[case(&[4, 100], &["愛x", "", ""])]
+    #[case(&[4, 2], &["愛x", "", ""])]
+    #[case(&[2, 3], &["", "愛", "x"])]
+    fn test_divide_offsets(#[case] offsets: &[usize], #[case] result: &[&str]) {
+        let lines = Text::from("愛x").divide(offsets);
+
+        assert_eq!(lines.iter().map(Text::plain).collect::<Vec<_>>(), result);
+    }
+
+    #[rstest]
+    #[case("foo\nbar", "\n", false, false, vec!["foo", "bar"])]
+    #[case("foo\nbar\n", "\n", false, false, vec!["foo", "bar"])]
+    #[case("foo\nbar\n", "\n", false, true, vec!["foo", "bar", ""])]
+    #[case("foo\nbar\n", "\n", true, false, vec!["foo\n", "bar\n"])]
+    #[case("foo\n\nbar", "\n", false, false, vec!["foo", "", "bar"])]
+    #[case("foo bar", "\n", false, false, vec!["foo bar"])]
+    fn test_split(
+        #[case] text: &str,
+        #[case] separator: &str,
+        #[case] include_separator: bool,
+        #[case] allow_blank: bool,
+        #[case] result: Vec<&str>,
+    ) {
+        let lines = Text::from(text).split(separator, include_separator, allow_blank);
+
+        assert_eq!(lines.iter().map(Text::plain).collect::<Vec<_>>(), result);
+    }
+
+    #[rstest]
+    #[case("foobarbaz", 6, OverflowMethod::Fold, false, "foobar")]
+    #[case("foobarbaz", 6, OverflowMethod::Crop, false, "foobar")]
+    #[case("foobarbaz", 6, OverflowMethod::Ellipsis, false, "fooba…")]
+    #[case("foobarbaz", 6, OverflowMethod::Ignore, false, "foobarbaz")]
+    #[case("foo", 6, OverflowMethod::Fold, false, "foo")]
+    #[case("foo", 6, OverflowMethod::Fold, true, "foo   ")]
+    #[case(
+        "这是对亚洲语言支持的测试",
+        7,
+        OverflowMethod::Ellipsis,
+        false,
+        "这是对…"
+    )]
+    fn test_truncate(
+        #[case] text: &str,
+        #[case] max_width: u32,
+        #[case] overflow: OverflowMethod,
+        #[case] pad: bool,
+        #[case] result: &str,
+    ) {
+        let mut text = Text::from(text);
+        text.truncate(max_width, Some(overflow), pad);
+
+        assert_eq!(text.plain(), result);
+    }
+
+    #[test]
+    fn test_pad() {
+        let mut text = Text::from("foo");
+        text.stylize(style("bold"), 0, None);
+        text.pad(2, '.');
+
+        assert_eq!(text.plain(), "..foo..");
+        assert_eq!(text.spans, [Span::new(2, 5, style("bold"))]);
+    }
+
+    #[test]
+    fn test_right_crop() {
+        let mut text = Text::from("foobar");
+        text.stylize(style("bold"), 2, None);
+        text.right_crop(2);
+
+        assert_eq!(text.plain(), "foob");
+        assert_eq!(text.spans, [Span::new(2, 4, style("bold"))]);
+    }
+
+    #[rstest]
+    #[case("foo   ", 4, "foo ")]
+    #[case("foo   ", 2, "foo")]
+    #[case("foo   ", 8, "foo   ")]
+    #[case("foobar", 4, "foobar")]
+    fn test_rstrip_end(#[case] text: &str, #[case] size: u32, #[case] result: &str) {
+        let mut text = Text::from(text);
+        text.rstrip_end(size);
+
+        assert_eq!(text.plain(), result);
+    }
+
+    #[rstest]
+    #[case("foo\tbar", 8, "foo     bar")]
+    #[case("\tfoo\tbar", 4, "    foo bar")]
+    #[case("foo\n\tbar", 4, "foo\n    bar")]
+    #[case("foobar", 4, "foobar")]
+    fn test_expand_tabs(#[case] text: &str, #[case] tab_size: u32, #[case] result: &str) {
+        let mut text = Text::from(text);
+        text.expand_tabs(tab_size);
+
+        assert_eq!(text.plain(), result);
+    }
+
+    #[test]
+    fn test_expand_tabs_extends_style() {
+        let mut text = Text::from("foo\tbar");
+        text.stylize(style("bold"), 0, Some(4));
+        text.expand_tabs(8);
+
+        assert_eq!(text.spans, [Span::new(0, 8, style("bold"))]);
+    }
+
+    #[rstest]
+    #[case("foo bar baz", 3, JustifyMethod::Default, OverflowMethod::Fold, vec!["foo", "bar", "baz"])]
+    #[case("foo bar baz", 4, JustifyMethod::Left, OverflowMethod::Fold, vec!["foo ", "bar ", "baz "])]
+    #[case("foo bar baz", 7, JustifyMethod::Center, OverflowMethod::Fold, vec!["foo bar", "  baz  "])]
+    #[case("foo bar baz", 7, JustifyMethod::Right, OverflowMethod::Fold, vec!["foo bar", "    baz"])]
+    #[case("foo bar baz", 10, JustifyMethod::Full, OverflowMethod::Fold, vec!["foo    bar", "baz"])]
+    #[case("foo bar baz egg", 9, JustifyMethod::Full, OverflowMethod::Fold, vec!["foo   bar", "baz egg"])]
+    #[case("foobarbaz", 4, JustifyMethod::Default, OverflowMethod::Fold, vec!["foob", "arba", "z"])]
+    #[case("foobarbaz", 4, JustifyMethod::Default, OverflowMethod::Crop, vec!["foob"])]
+    #[case("foobarbaz", 4, JustifyMethod::Default, OverflowMethod::Ellipsis, vec!["foo…"])]
+    #[case("foobarbaz", 4, JustifyMethod::Default, OverflowMethod::Ignore, vec!["foobarbaz"])]
+    #[case("foo\n\nbar", 10, JustifyMethod::Default, OverflowMethod::Fold, vec!["foo", "", "bar"])]
+    #[case("这是对亚洲语言支持的测试", 8, JustifyMethod::Default, OverflowMethod::Fold, vec!["这是对亚", "洲语言支", "持的测试"])]
+    fn test_wrap(
+        #[case] text: &str,
+        #[case] width: u32,
+        #[case] justify: JustifyMethod,
+        #[case] overflow: OverflowMethod,
+        #[case] result: Vec<&str>,
+    ) {
+        let lines = Text::from(text).wrap(width, Some(justify), Some(overflow));
+
+        assert_eq!(lines.iter().map(Text::plain).collect::<Vec<_>>(), result);
+    }
+
+    #[test]
+    fn test_wrap_no_wrap() {
+        let text = Text {
+            no_wrap: Some(true),
+            ..Text::from("foo bar baz")
+        };
+
+        assert_eq!(
+            text.wrap(4, None, Some(OverflowMethod::Crop))
+                .iter()
+                .map(Text::plain)
+                .collect::<Vec<_>>(),
+            ["foo "]
+        );
+    }
+
+    #[test]
+    fn test_wrap_full_keeps_styles() {
+        let mut text = Text::from("foo bar baz");
+        text.stylize(style("bold"), 0, Some(7));
+        let lines = text.wrap(9, Some(JustifyMethod::Full), None);
+
+        assert_eq!(lines[0].plain(), "foo   bar");
+        assert_eq!(
+            lines[0].spans,
+            [
+                Span::new(0, 3, style("bold")),
+                Span::new(3, 6, style("bold")),
+                Span::new(6, 9, style("bold")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_wraps() {
+        let console = Console::new().with_width(7);
+        let text = Text {
+            justify: Some(JustifyMethod::Center),
+            ..Text::new("foo bar baz", style("bold"))
+        };
+
+        assert_eq!(
+            console.render(&text, None),
+            [
+                Segment::new("foo bar", Some(style("bold"))),
+                Segment::new("\n", None),
+                Segment::new("  baz  ", Some(style("bold"))),
+                Segment::line(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,582 @@
+//! Load [`TerminalTheme`]s from the theme files of other terminals.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::Cursor;
+
+use serde_json::Value;
+
+use crate::color_triplet::ColorTriplet;
+use crate::terminal_theme::TerminalTheme;
+
+/// Names of the 8 ANSI colors, as used by Alacritty.
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Names of the 16 ANSI colors, as used by Windows Terminal.
+const WINDOWS_TERMINAL_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "purple",
+    "cyan",
+    "white",
+    "brightBlack",
+    "brightRed",
+    "brightGreen",
+    "brightYellow",
+    "brightBlue",
+    "brightPurple",
+    "brightCyan",
+    "brightWhite",
+];
+
+/// The base16 colors used for the 16 ANSI colors, as mapped by base16-shell.
+const BASE16_ANSI_NAMES: [&str; 16] = [
+    "base00", "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base05", "base03",
+    "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base07",
+];
+
+/// A theme file could not be parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ThemeParseError {
+    /// The file isn't valid in its format, with the parser's error message.
+    Syntax(String),
+    /// A color is missing from the theme, with the name of its key.
+    MissingColor(String),
+    /// A color isn't in a recognized format, with the name of its key and its
+    /// value.
+    InvalidColor(String, String),
+}
+
+impl Display for ThemeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(message) => write!(f, "invalid theme file: {message}"),
+            Self::MissingColor(key) => write!(f, "theme is missing the color {key:?}"),
+            Self::InvalidColor(key, value) => {
+                write!(f, "{value:?} is not a valid color for {key:?}")
+            },
+        }
+    }
+}
+
+impl Error for ThemeParseError {}
+
+impl TerminalTheme {
+    /// Parse an iTerm2 color preset (`.itermcolors` file).
+    ///
+    /// # Arguments
+    ///
+    /// * `plist` - Contents of the preset, an XML property list.
+    ///
+    /// # Errors
+    ///
+    /// [`ThemeParseError`] if the preset can't be parsed, or is missing any of
+    /// the background, foreground or 16 ANSI colors. The cursor and selection
+    /// colors are optional, and ignored if they can't be parsed.
+    pub fn from_iterm(plist: &str) -> Result<Self, ThemeParseError> {
+        let value: Value = plist::from_reader(Cursor::new(plist))
+            .map_err(|error| ThemeParseError::Syntax(error.to_string()))?;
+
+        let color = |key: &str| {
+            let color = value
+                .get(key)
+                .ok_or_else(|| ThemeParseError::MissingColor(key.into()))?;
+            let component = |name: &str| {
+                color
+                    .get(format!("{name} Component"))
+                    .and_then(Value::as_f64)
+                    .filter(|component| (0.0..=1.0).contains(component))
+                    .ok_or_else(|| ThemeParseError::InvalidColor(key.into(), color.to_string()))
+            };
+
+            #[allow(clippy::cast_possible_truncation)]
+            #[allow(clippy::cast_sign_loss)]
+            let to_u8 = |component: f64| (component * 255.0).round() as u8;
+
+            Ok(ColorTriplet::new(
+                to_u8(component("Red")?),
+                to_u8(component("Green")?),
+                to_u8(component("Blue")?),
+            ))
+        };
+
+        let ansi_colors = ansi_colors(|index| color(&format!("Ansi {index} Color")))?;
+
+        let optional_color = |key: &str| color(key).ok();
+
+        Ok(Self {
+            cursor_color: optional_color("Cursor Color"),
+            selection_background: optional_color("Selection Color"),
+            selection_foreground: optional_color("Selected Text Color"),
+            ..Self::from_triplets(
+                color("Background Color")?,
+                color("Foreground Color")?,
+                ansi_colors,
+            )
+        })
+    }
+
+    /// Parse a Windows Terminal color scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - A color scheme object, as found in the `schemes` list of the
+    ///   Windows Terminal settings.
+    ///
+    /// # Errors
+    ///
+    /// [`ThemeParseError`] if the scheme can't be parsed, or is missing any of
+    /// the background, foreground or 16 ANSI colors. The cursor and selection
+    /// colors are optional, and ignored if they can't be parsed.
+    pub fn from_windows_terminal(json: &str) -> Result<Self, ThemeParseError> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|error| ThemeParseError::Syntax(error.to_string()))?;
+
+        let ansi_colors = ansi_colors(|index| hex_color(&value, &[WINDOWS_TERMINAL_NAMES[index]]))?;
+
+        Ok(Self {
+            cursor_color: hex_color(&value, &["cursorColor"]).ok(),
+            selection_background: hex_color(&value, &["selectionBackground"]).ok(),
+            ..Self::from_triplets(
+                hex_color(&value, &["background"])?,
+                hex_color(&value, &["foreground"])?,
+                ansi_colors,
+            )
+        })
+    }
+
+    /// Parse an Alacritty TOML configuration file.
+    ///
+    /// Colors are read from the `colors.primary`, `colors.normal` and
+    /// `colors.bright` tables. If there is no `colors.bright` table, the normal
+    /// colors are used for the bright colors too. The optional
+    /// `colors.cursor.cursor`, `colors.selection.background` and
+    /// `colors.selection.text` colors are ignored if they aren't hex colors,
+    /// such as `CellForeground`.
+    ///
+    /// # Arguments
+    ///
+    /// * `toml` - Contents of the configuration file.
+    ///
+    /// # Errors
+    ///
+    /// [`ThemeParseError`] if the file can't be parsed, or is missing any of
+    /// the primary or normal colors.
+    pub fn from_alacritty_toml(toml: &str) -> Result<Self, ThemeParseError> {
+        let value: Value =
+            toml::from_str(toml).map_err(|error| ThemeParseError::Syntax(error.to_string()))?;
+
+        Self::from_alacritty(&value)
+    }
+
+    /// Parse an Alacritty YAML configuration file, the format used before
+    /// Alacritty 0.13.
+    ///
+    /// Colors are read in the same way as
+    /// [`TerminalTheme::from_alacritty_toml`].
+    ///
+    /// # Arguments
+    ///
+    /// * `yaml` - Contents of the configuration file.
+    ///
+    /// # Errors
+    ///
+    /// [`ThemeParseError`] if the file can't be parsed, or is missing any of
+    /// the primary or normal colors.
+    pub fn from_alacritty_yaml(yaml: &str) -> Result<Self, ThemeParseError> {
+        let value: Value = serde_norway::from_str(yaml)
+            .map_err(|error| ThemeParseError::Syntax(error.to_string()))?;
+
+        Self::from_alacritty(&value)
+    }
+
+    /// Get a theme from a parsed Alacritty configuration file.
+    fn from_alacritty(value: &Value) -> Result<Self, ThemeParseError> {
+        let bright_section = if lookup(value, &["colors", "bright"]).is_some() {
+            "bright"
+        } else {
+            "normal"
+        };
+
+        let ansi_colors = ansi_colors(|index| {
+            let section = if index < 8 { "normal" } else { bright_section };
+            hex_color(value, &["colors", section, ANSI_NAMES[index % 8]])
+        })?;
+
+        Ok(Self {
+            cursor_color: hex_color(value, &["colors", "cursor", "cursor"]).ok(),
+            selection_background: hex_color(value, &["colors", "selection", "background"]).ok(),
+            selection_foreground: hex_color(value, &["colors", "selection", "text"]).ok(),
+            ..Self::from_triplets(
+                hex_color(value, &["colors", "primary", "background"])?,
+                hex_color(value, &["colors", "primary", "foreground"])?,
+                ansi_colors,
+            )
+        })
+    }
+
+    /// Parse a base16 scheme.
+    ///
+    /// Both the original format, with the colors at the top level, and the
+    /// newer format, with the colors in a `palette` mapping, are accepted. The
+    /// colors are mapped to ANSI colors in the same way as base16-shell.
+    ///
+    /// # Arguments
+    ///
+    /// * `yaml` - Contents of the scheme file.
+    ///
+    /// # Errors
+    ///
+    /// [`ThemeParseError`] if the scheme can't be parsed, or is missing any of
+    /// the colors used.
+    pub fn from_base16(yaml: &str) -> Result<Self, ThemeParseError> {
+        let value: Value = serde_norway::from_str(yaml)
+            .map_err(|error| ThemeParseError::Syntax(error.to_string()))?;
+        let palette = value.get("palette").unwrap_or(&value);
+
+        let ansi_colors = ansi_colors(|index| hex_color(palette, &[BASE16_ANSI_NAMES[index]]))?;
+
+        Ok(Self::from_triplets(
+            hex_color(palette, &["base00"])?,
+            hex_color(palette, &["base05"])?,
+            ansi_colors,
+        ))
+    }
+}
+
+/// Get the 16 ANSI colors of a theme, stopping at the first error.
+fn ansi_colors(
+    color: impl Fn(usize) -> Result<ColorTriplet, ThemeParseError>,
+) -> Result<[ColorTriplet; 16], ThemeParseError> {
+    let colors: Vec<ColorTriplet> = (0..16).map(color).collect::<Result<_, _>>()?;
+
+    Ok(colors.try_into().unwrap())
+}
+
+/// Look up a value by its path of keys.
+fn lookup<'a>(value: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, key| value.get(key))
+}
+
+/// Look up and parse a hex color such as `#ff8800`, `0xff8800` or `ff8800`.
+fn hex_color(value: &Value, path: &[&str]) -> Result<ColorTriplet, ThemeParseError> {
+    let key = path.join(".");
+    let color = lookup(value, path).ok_or_else(|| ThemeParseError::MissingColor(key.clone()))?;
+    let invalid = || {
+        let value = color
+            .as_str()
+            .map_or_else(|| color.to_string(), String::from);
+        ThemeParseError::InvalidColor(key.clone(), value)
+    };
+
+    let hex = color.as_str().ok_or_else(invalid)?.trim();
+    let hex = hex
+        .strip_prefix('#')
+        .or_else(|| hex.strip_prefix("0x"))
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+    if hex.len() != 6 || !hex.chars().all(|character| character.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let component = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).unwrap();
+
+    Ok(ColorTriplet::new(component(0), component(2), component(4)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build an iTerm2 preset where ANSI color `n` is `(n, n, n)`.
+    fn iterm_plist(missing: Option<&str>) -> String {
+        let color = |key: String, value: f64| {
+            if missing == Some(key.as_str()) {
+                return String::new();
+            }
+            format!(
+                "<key>{key}</key><dict><key>Color Space</key><string>sRGB</string><key>Red \
+                 Component</key><real>{value}</real><key>Green \
+                 Component</key><real>{value}</real><key>Blue \
+                 Component</key><real>{value}</real></dict>"
+            )
+        };
+        let mut colors =
+            color("Background Color".into(), 1.0) + &color("Foreground Color".into(), 0.0);
+        for index in 0..16 {
+            colors += &color(format!("Ansi {index} Color"), f64::from(index) / 255.0);
+        }
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+             \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\
+             <plist version=\"1.0\"><dict>{colors}</dict></plist>"
+        )
+    }
+
+    #[test]
+    fn test_from_iterm() {
+        let theme = TerminalTheme::from_iterm(&iterm_plist(None)).unwrap();
+
+        assert_eq!(theme.background_color, ColorTriplet::new(255, 255, 255));
+        assert_eq!(theme.foreground_color, ColorTriplet::new(0, 0, 0));
+        for (index, color) in (0..16).zip(theme.ansi_colors()) {
+            assert_eq!(*color, ColorTriplet::new(index, index, index));
+        }
+    }
+
+    #[test]
+    fn test_from_iterm_errors() {
+        assert_eq!(
+            TerminalTheme::from_iterm(&iterm_plist(Some("Ansi 12 Color"))),
+            Err(ThemeParseError::MissingColor("Ansi 12 Color".into()))
+        );
+        assert!(matches!(
+            TerminalTheme::from_iterm("<plist>"),
+            Err(ThemeParseError::Syntax(_))
+        ));
+    }
+
+    const WINDOWS_TERMINAL_SCHEME: &str = r##"{
+        "name": "Campbell",
+        "background": "#0C0C0C",
+        "foreground": "#CCCCCC",
+        "black": "#0C0C0C",
+        "red": "#C50F1F",
+        "green": "#13A10E",
+        "yellow": "#C19C00",
+        "blue": "#0037DA",
+        "purple": "#881798",
+        "cyan": "#3A96DD",
+        "white": "#CCCCCC",
+        "brightBlack": "#767676",
+        "brightRed": "#E74856",
+        "brightGreen": "#16C60C",
+        "brightYellow": "#F9F1A5",
+        "brightBlue": "#3B78FF",
+        "brightPurple": "#B4009E",
+        "brightCyan": "#61D6D6",
+        "brightWhite": "#F2F2F2"
+    }"##;
+
+    #[test]
+    fn test_from_windows_terminal() {
+        let theme = TerminalTheme::from_windows_terminal(WINDOWS_TERMINAL_SCHEME).unwrap();
+
+        assert_eq!(theme.background_color, ColorTriplet::new(12, 12, 12));
+        assert_eq!(theme.foreground_color, ColorTriplet::new(204, 204, 204));
+        assert_eq!(theme.ansi_colors()[5], ColorTriplet::new(136, 23, 152));
+        assert_eq!(theme.ansi_colors()[15], ColorTriplet::new(242, 242, 242));
+    }
+
+    #[test]
+    fn test_from_windows_terminal_errors() {
+        assert_eq!(
+            TerminalTheme::from_windows_terminal(
+                &WINDOWS_TERMINAL_SCHEME.replace("\"brightCyan\"", "\"brightTeal\"")
+            ),
+            Err(ThemeParseError::MissingColor("brightCyan".into()))
+        );
+        assert_eq!(
+            TerminalTheme::from_windows_terminal(
+                &WINDOWS_TERMINAL_SCHEME.replace("#13A10E", "green")
+            ),
+            Err(ThemeParseError::InvalidColor(
+                "green".into(),
+                "green".into()
+            ))
+        );
+        assert!(matches!(
+            TerminalTheme::from_windows_terminal("{"),
+            Err(ThemeParseError::Syntax(_))
+        ));
+    }
+
+    const ALACRITTY_TOML: &str = r##"
+        [colors.primary]
+        background = "#1d1f21"
+        foreground = "#c5c8c6"
+
+        [colors.normal]
+        black = "#1d1f21"
+        red = "#cc6666"
+        green = "#b5bd68"
+        yellow = "#f0c674"
+        blue = "#81a2be"
+        magenta = "#b294bb"
+        cyan = "#8abeb7"
+        white = "#c5c8c6"
+
+        [colors.bright]
+        black = "#666666"
+        red = "#d54e53"
+        green = "#b9ca4a"
+        yellow = "#e7c547"
+        blue = "#7aa6da"
+        magenta = "#c397d8"
+        cyan = "#70c0b1"
+        white = "#eaeaea"
+    "##;
+
+    #[test]
+    fn test_from_alacritty_toml() {
+        let theme = TerminalTheme::from_alacritty_toml(ALACRITTY_TOML).unwrap();
+
+        assert_eq!(theme.background_color, ColorTriplet::new(29, 31, 33));
+        assert_eq!(theme.ansi_colors()[1], ColorTriplet::new(204, 102, 102));
+        assert_eq!(theme.ansi_colors()[15], ColorTriplet::new(234, 234, 234));
+    }
+
+    #[test]
+    fn test_from_alacritty_toml_without_bright() {
+        let toml = &ALACRITTY_TOML[..ALACRITTY_TOML.find("[colors.bright]").unwrap()];
+        let theme = TerminalTheme::from_alacritty_toml(toml).unwrap();
+
+        assert_eq!(theme.ansi_colors()[..8], theme.ansi_colors()[8..]);
+    }
+
+    #[test]
+    fn test_from_alacritty_toml_errors() {
+        assert_eq!(
+            TerminalTheme::from_alacritty_toml(&ALACRITTY_TOML.replacen("yellow", "orange", 1)),
+            Err(ThemeParseError::MissingColor("colors.normal.yellow".into()))
+        );
+        assert_eq!(
+            TerminalTheme::from_alacritty_toml(&ALACRITTY_TOML.replace("#eaeaea", "#eaeae")),
+            Err(ThemeParseError::InvalidColor(
+                "colors.bright.white".into(),
+                "#eaeae".into()
+            ))
+        );
+        assert!(matches!(
+            TerminalTheme::from_alacritty_toml("[colors"),
+            Err(ThemeParseError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_alacritty_yaml() {
+        let yaml = "
+colors:
+  primary:
+    background: '0x1d1f21'
+    foreground: '0xc5c8c6'
+  normal:
+    black:   '0x1d1f21'
+    red:     '0xcc6666'
+    green:   '0xb5bd68'
+    yellow:  '0xf0c674'
+    blue:    '0x81a2be'
+    magenta: '0xb294bb'
+    cyan:    '0x8abeb7'
+    white:   '0xc5c8c6'
+";
+        let theme = TerminalTheme::from_alacritty_yaml(yaml).unwrap();
+
+        assert_eq!(
+            theme,
+            TerminalTheme::from_alacritty_toml(
+                &ALACRITTY_TOML[..ALACRITTY_TOML.find("[colors.bright]").unwrap()]
+            )
+            .unwrap()
+        );
+        assert!(matches!(
+            TerminalTheme::from_alacritty_yaml("colors: ["),
+            Err(ThemeParseError::Syntax(_))
+        ));
+    }
+
+    const BASE16_YAML: &str = r#"
+scheme: "Tomorrow Night"
+author: "Chris Kempson (http://chriskempson.com)"
+base00: "1d1f21"
+base01: "282a2e"
+base02: "373b41"
+base03: "969896"
+base04: "b4b7b4"
+base05: "c5c8c6"
+base06: "e0e0e0"
+base07: "ffffff"
+base08: "cc6666"
+base09: "de935f"
+base0A: "f0c674"
+base0B: "b5bd68"
+base0C: "8abeb7"
+base0D: "81a2be"
+base0E: "b294bb"
+base0F: "a3685a"
+"#;
+
+    #[test]
+    fn test_from_base16() {
+        let theme = TerminalTheme::from_base16(BASE16_YAML).unwrap();
+
+        assert_eq!(theme.background_color, ColorTriplet::new(29, 31, 33));
+        assert_eq!(theme.foreground_color, ColorTriplet::new(197, 200, 198));
+        assert_eq!(theme.ansi_colors()[1], ColorTriplet::new(204, 102, 102));
+        assert_eq!(theme.ansi_colors()[8], ColorTriplet::new(150, 152, 150));
+        assert_eq!(theme.ansi_colors()[15], ColorTriplet::new(255, 255, 255));
+    }
+
+    #[test]
+    fn test_from_base16_palette() {
+        let yaml = BASE16_YAML.replace("\nbase", "\n  base").replacen(
+            "\n  base00",
+            "\npalette:\n  base00",
+            1,
+        );
+
+        assert_eq!(
+            TerminalTheme::from_base16(&yaml),
+            TerminalTheme::from_base16(BASE16_YAML)
+        );
+    }
+
+    #[test]
+    fn test_from_base16_errors() {
+        assert_eq!(
+            TerminalTheme::from_base16(&BASE16_YAML.replace("base0C", "base0c")),
+            Err(ThemeParseError::MissingColor("base0C".into()))
+        );
+    }
+
+    #[test]
+    fn test_theme_parse_error_display() {
+        assert_eq!(
+            ThemeParseError::MissingColor("base0C".into()).to_string(),
+            "theme is missing the color \"base0C\""
+        );
+        assert_eq!(
+            ThemeParseError::InvalidColor("red".into(), "#ff".into()).to_string(),
+            "\"#ff\" is not a valid color for \"red\""
+        );
+    }
+
+    #[test]
+    fn test_optional_colors() {
+        let theme = TerminalTheme::from_windows_terminal(&WINDOWS_TERMINAL_SCHEME.replace(
+            "\"name\"",
+            "\"cursorColor\": \"#FFFFFF\", \"selectionBackground\": \"oops\", \"name\"",
+        ))
+        .unwrap();
+
+        assert_eq!(theme.cursor_color, Some(ColorTriplet::new(255, 255, 255)));
+        assert_eq!(theme.selection_background, None);
+
+        let toml = format!(
+            "{ALACRITTY_TOML}\n[colors.cursor]\ncursor = \
+             \"CellForeground\"\n[colors.selection]\nbackground = \"#010203\"\ntext = \"#040506\""
+        );
+        let theme = TerminalTheme::from_alacritty_toml(&toml).unwrap();
+
+        assert_eq!(theme.cursor_color, None);
+        assert_eq!(theme.selection_background, Some(ColorTriplet::new(1, 2, 3)));
+        assert_eq!(theme.selection_foreground, Some(ColorTriplet::new(4, 5, 6)));
+    }
+}
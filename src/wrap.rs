@@ -0,0 +1,101 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::cells::{cell_len, get_character_cell_size};
+
+lazy_static! {
+    static ref RE_WORD: Regex = Regex::new(r"\s*\S+\s*").unwrap();
+}
+
+/// Split a word into chunks that fit within `width` cells, returning the byte
+/// offset of each chunk within the word along with the chunk.
+fn chop_word(word: &str, width: u32) -> Vec<(usize, &str)> {
+    let mut chunks = vec![];
+    let mut chunk_start = 0;
+    let mut chunk_width = 0;
+
+    for (offset, character) in word.char_indices() {
+        let size = get_character_cell_size(character);
+
+        if chunk_width + size > width && offset > chunk_start {
+            chunks.push((chunk_start, &word[chunk_start..offset]));
+            chunk_start = offset;
+            chunk_width = 0;
+        }
+        chunk_width += size;
+    }
+    chunks.push((chunk_start, &word[chunk_start..]));
+
+    chunks
+}
+
+/// Get the byte offsets where a line of text should be divided so that each
+/// line fits within `width` cells.
+///
+/// # Arguments
+///
+/// * `text` - The text to divide, which should not contain new lines.
+/// * `width` - Maximum width of a line in cells.
+/// * `fold` - Fold words longer than `width` on to the next line.
+pub(crate) fn divide_line(text: &str, width: u32, fold: bool) -> Vec<usize> {
+    let mut divides = vec![];
+    let mut line_position = 0;
+
+    for word_match in RE_WORD.find_iter(text) {
+        let start = word_match.start();
+        let word = word_match.as_str();
+        let word_length = cell_len(word.trim_end());
+
+        if line_position + word_length > width {
+            if word_length > width {
+                if fold {
+                    for (offset, chunk) in chop_word(word, width) {
+                        if start + offset > 0 {
+                            divides.push(start + offset);
+                        }
+                        line_position = cell_len(chunk);
+                    }
+                } else {
+                    if start > 0 {
+                        divides.push(start);
+                    }
+                    line_position = cell_len(word);
+                }
+            } else if line_position > 0 && start > 0 {
+                divides.push(start);
+                line_position = cell_len(word);
+            }
+        } else {
+            line_position += cell_len(word);
+        }
+    }
+
+    divides
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", 10, true, vec![])]
+    #[case("foo bar baz", 10, true, vec![8])]
+    #[case("foo bar baz", 7, true, vec![8])]
+    #[case("foo bar baz", 3, true, vec![4, 8])]
+    #[case("foo bar baz", 20, true, vec![])]
+    #[case("foobarbaz", 4, true, vec![4, 8])]
+    #[case("foobarbaz", 4, false, vec![])]
+    #[case("foo foobarbaz", 4, true, vec![4, 8, 12])]
+    #[case("foo foobarbaz", 4, false, vec![4])]
+    #[case("这是对亚洲语言支持的测试", 8, true, vec![12, 24])]
+    fn test_divide_line(
+        #[case] text: &str,
+        #[case] width: u32,
+        #[case] fold: bool,
+        #[case] result: Vec<usize>,
+    ) {
+        assert_eq!(divide_line(text, width, fold), result);
+    }
+}